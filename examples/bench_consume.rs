@@ -0,0 +1,43 @@
+//! Compares `count_byte` with a loop which advances byte by byte.
+//!
+//! Run with `cargo run --release --example bench_consume`.
+
+use byte_parser::{StrParser, ParseIterator};
+
+use std::time::Instant;
+
+fn main() {
+
+	let block = " ".repeat(4096) + "x";
+	let input = block.repeat(2 * 1024);
+
+	let start = Instant::now();
+	let mut parser = StrParser::new(&input);
+	let mut count_fast = 0;
+	loop {
+		count_fast += parser.count_byte(b' ');
+		if parser.next().is_none() {
+			break
+		}
+	}
+	let fast_time = start.elapsed();
+
+	let start = Instant::now();
+	let mut parser = StrParser::new(&input);
+	let mut count_naive = 0;
+	loop {
+		while parser.next_if(|&b| b == b' ').is_some() {
+			count_naive += 1;
+		}
+		if parser.next().is_none() {
+			break
+		}
+	}
+	let naive_time = start.elapsed();
+
+	assert_eq!(count_fast, count_naive);
+	println!("input:      {} bytes", input.len());
+	println!("count_byte: {:?}", fast_time);
+	println!("naive:      {:?}", naive_time);
+
+}
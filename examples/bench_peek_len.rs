@@ -0,0 +1,39 @@
+//! Compares `peek_len` on a `StrParser`, which advances in one step, with
+//! an adapter which needs to call `advance` for every byte.
+//!
+//! Run with `cargo run --release --example bench_peek_len`.
+
+use byte_parser::{StrParser, ParseIterator};
+
+use std::time::Instant;
+
+fn main() {
+
+	let input = "a".repeat(1024 * 1024);
+	let len = input.len() / 2;
+	let rounds = 100;
+
+	let start = Instant::now();
+	let mut parser = StrParser::new(&input);
+	let mut total_fast = 0;
+	for _ in 0..rounds {
+		total_fast += parser.peek_len(len).unwrap().len();
+	}
+	let fast_time = start.elapsed();
+
+	let start = Instant::now();
+	let mut parser = StrParser::new(&input);
+	// ignore_byte does not know how far one advance goes
+	let mut parser = parser.ignore_byte(0);
+	let mut total_slow = 0;
+	for _ in 0..rounds {
+		total_slow += parser.peek_len(len).unwrap().len();
+	}
+	let slow_time = start.elapsed();
+
+	assert_eq!(total_fast, total_slow);
+	println!("input:         {} bytes, {} rounds", input.len(), rounds);
+	println!("StrParser:     {:?}", fast_time);
+	println!("ignore_byte:   {:?}", slow_time);
+
+}
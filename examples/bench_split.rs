@@ -0,0 +1,39 @@
+//! Compares `split_on_byte` searching a word at a time with the byte by
+//! byte path `split_on_byte` used before.
+//!
+//! The fast path is only taken if the inner iterator advances by one byte,
+//! wrapping the parser in a `while_byte_fn` which accepts every byte hides
+//! that and runs the old `advance` loop over the same input.
+//!
+//! Run with `cargo run --release --example bench_split`.
+
+use byte_parser::{StrParser, ParseIterator};
+
+use std::time::Instant;
+
+fn main() {
+
+	let line = "a line with some content in a log file, nothing special\n";
+	let input = line.repeat(8 * 1024 * 1024 / line.len());
+
+	let start = Instant::now();
+	let mut parser = StrParser::new(&input);
+	let mut len_fast = 0;
+	parser.split_on_byte(b'\n')
+		.for_each(|line| len_fast += line.record().consume_to_str().len());
+	let fast_time = start.elapsed();
+
+	let start = Instant::now();
+	let mut parser = StrParser::new(&input);
+	let mut len_slow = 0;
+	parser.while_byte_fn(|_| true)
+		.split_on_byte(b'\n')
+		.for_each(|line| len_slow += line.record().consume_to_str().len());
+	let slow_time = start.elapsed();
+
+	assert_eq!(len_fast, len_slow);
+	println!("input:          {} bytes", input.len());
+	println!("word at a time: {:?}", fast_time);
+	println!("byte by byte:   {:?}", slow_time);
+
+}
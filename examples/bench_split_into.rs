@@ -0,0 +1,41 @@
+//! Compares `split_into` with `map_and_collect` into a `Vec`.
+//!
+//! Run with `cargo run --release --example bench_split_into`.
+
+use byte_parser::{StrParser, ParseIterator};
+
+use std::time::Instant;
+
+fn main() {
+
+	let line = "2021-06-01,12:00:00,INFO,server,started\n";
+	let input = line.repeat(8 * 1024 * 1024 / line.len());
+
+	let start = Instant::now();
+	let mut parser = StrParser::new(&input);
+	let mut len_into = 0;
+	parser.split_on_byte(b'\n')
+		.for_each(|line| {
+			let mut fields = [&b""[..]; 5];
+			let n = line.split_into(b',', &mut fields);
+			len_into += fields[..n].iter().map(|f| f.len()).sum::<usize>();
+		});
+	let into_time = start.elapsed();
+
+	let start = Instant::now();
+	let mut parser = StrParser::new(&input);
+	let mut len_collect = 0;
+	parser.split_on_byte(b'\n')
+		.for_each(|line| {
+			let fields: Vec<_> = line.split_on_byte(b',')
+				.map_and_collect(|f| f.record().consume_to_slice());
+			len_collect += fields.iter().map(|f| f.len()).sum::<usize>();
+		});
+	let collect_time = start.elapsed();
+
+	assert_eq!(len_into, len_collect);
+	println!("input:           {} bytes", input.len());
+	println!("split_into:      {:?}", into_time);
+	println!("map_and_collect: {:?}", collect_time);
+
+}
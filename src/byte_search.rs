@@ -0,0 +1,94 @@
+//! Word at a time byte searches used by fast paths.
+
+use core::mem::size_of;
+
+const WORD: usize = size_of::<usize>();
+// 0x0101..01
+const LO: usize = usize::MAX / 255;
+// 0x8080..80
+const HI: usize = LO * 0x80;
+
+#[inline]
+fn read_word(chunk: &[u8]) -> usize {
+	let mut bytes = [0; WORD];
+	bytes.copy_from_slice(chunk);
+	usize::from_ne_bytes(bytes)
+}
+
+/// Returns `true` if any byte in `word` is zero.
+#[inline]
+fn has_zero_byte(word: usize) -> bool {
+	word.wrapping_sub(LO) & !word & HI != 0
+}
+
+/// Returns the index of the first `byte` in `haystack`.
+pub(crate) fn find_byte(byte: u8, haystack: &[u8]) -> Option<usize> {
+	let repeated = LO * byte as usize;
+	let mut i = 0;
+
+	// skip every word which does not contain the byte
+	for chunk in haystack.chunks_exact(WORD) {
+		if has_zero_byte(read_word(chunk) ^ repeated) {
+			break
+		}
+		i += WORD;
+	}
+
+	haystack[i..].iter()
+		.position(|&b| b == byte)
+		.map(|p| p + i)
+}
+
+
+/// Returns the index of the first byte which is not `byte`.
+pub(crate) fn find_not_byte(byte: u8, haystack: &[u8]) -> Option<usize> {
+	let repeated = LO * byte as usize;
+	let mut i = 0;
+
+	// skip every word which only contains the byte
+	for chunk in haystack.chunks_exact(WORD) {
+		if read_word(chunk) != repeated {
+			break
+		}
+		i += WORD;
+	}
+
+	haystack[i..].iter()
+		.position(|&b| b != byte)
+		.map(|p| p + i)
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+	use super::*;
+
+	#[test]
+	fn same_as_naive() {
+
+		let mut haystack = [b'a'; 40];
+		for i in 0..40 {
+			for &b in [b'a', b'b', 0, 0x80, 0xff].iter() {
+				haystack[i] = b;
+				for len in [0, 1, 7, 8, 9, 16, 17, 33, 40].iter() {
+					let hay = &haystack[..*len];
+					for &needle in [b'a', b'b', 0, 0x80, 0xff, 0x7f].iter() {
+						assert_eq!(
+							find_byte(needle, hay),
+							hay.iter().position(|&b| b == needle)
+						);
+						assert_eq!(
+							find_not_byte(needle, hay),
+							hay.iter().position(|&b| b != needle)
+						);
+					}
+				}
+				haystack[i] = b'a';
+			}
+		}
+
+	}
+
+}
@@ -0,0 +1,159 @@
+//!
+//! A set of bytes which can be used as a byte class.
+//!
+//! ## Example
+//! ```
+//! # use byte_parser::{StrParser, ParseIterator, byte_set::ByteSet};
+//! let ident = ByteSet::new().alphanumeric().byte(b'_');
+//!
+//! let mut parser = StrParser::new("my_ident2 = 1");
+//! let s = parser
+//! 	.record()
+//! 	.consume_class(&ident)
+//! 	.to_str();
+//! assert_eq!(s, "my_ident2");
+//! ```
+
+use core::ops::RangeInclusive;
+
+
+/// A set of bytes backed by a 256 bit bitmap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ByteSet {
+	bits: [u64; 4]
+}
+
+impl ByteSet {
+
+	/// Creates an empty `ByteSet`.
+	pub const fn new() -> Self {
+		Self { bits: [0; 4] }
+	}
+
+	/// Creates a `ByteSet` containing every byte in the slice.
+	pub fn from_slice(bytes: &[u8]) -> Self {
+		Self::new().bytes(bytes)
+	}
+
+	/// Adds a byte to the set.
+	#[inline]
+	pub fn insert(&mut self, byte: u8) {
+		self.bits[(byte >> 6) as usize] |= 1 << (byte & 63);
+	}
+
+	/// Returns `true` if the set contains the byte.
+	#[inline]
+	pub fn contains(&self, byte: u8) -> bool {
+		self.bits[(byte >> 6) as usize] & (1 << (byte & 63)) != 0
+	}
+
+	/// Returns `true` if the set contains no bytes.
+	pub fn is_empty(&self) -> bool {
+		self.bits == [0; 4]
+	}
+
+	/// Adds a byte.
+	pub fn byte(mut self, byte: u8) -> Self {
+		self.insert(byte);
+		self
+	}
+
+	/// Adds every byte in the slice.
+	pub fn bytes(mut self, bytes: &[u8]) -> Self {
+		for &b in bytes {
+			self.insert(b);
+		}
+		self
+	}
+
+	/// Adds every byte in the range.
+	pub fn range(mut self, range: RangeInclusive<u8>) -> Self {
+		for b in range {
+			self.insert(b);
+		}
+		self
+	}
+
+	/// Adds every byte of another set.
+	pub fn union(mut self, other: &Self) -> Self {
+		for (a, b) in self.bits.iter_mut().zip(other.bits.iter()) {
+			*a |= b;
+		}
+		self
+	}
+
+	/// Adds `0-9`.
+	pub fn digit(self) -> Self {
+		self.range(b'0'..=b'9')
+	}
+
+	/// Adds `0-9`, `a-f` and `A-F`.
+	pub fn hex_digit(self) -> Self {
+		self.digit()
+			.range(b'a'..=b'f')
+			.range(b'A'..=b'F')
+	}
+
+	/// Adds `a-z` and `A-Z`.
+	pub fn alpha(self) -> Self {
+		self.range(b'a'..=b'z')
+			.range(b'A'..=b'Z')
+	}
+
+	/// Adds `0-9`, `a-z` and `A-Z`.
+	pub fn alphanumeric(self) -> Self {
+		self.digit().alpha()
+	}
+
+	/// Adds every byte for which `u8::is_ascii_whitespace` returns `true`.
+	pub fn whitespace(self) -> Self {
+		self.bytes(b" \t\n\x0C\r")
+	}
+
+}
+
+impl From<&[u8]> for ByteSet {
+	fn from(bytes: &[u8]) -> Self {
+		Self::from_slice(bytes)
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+
+	use super::*;
+
+	#[test]
+	fn contains() {
+
+		let set = ByteSet::from_slice(b"\x00a\x7f\x80\xff");
+		for b in 0..=255u8 {
+			assert_eq!(set.contains(b), b"\x00a\x7f\x80\xff".contains(&b));
+		}
+
+		assert!(ByteSet::new().is_empty());
+		assert!(!set.is_empty());
+
+	}
+
+	#[test]
+	fn classes() {
+
+		let ws = ByteSet::new().whitespace();
+		let hex = ByteSet::new().hex_digit();
+		let alnum = ByteSet::new().alphanumeric();
+
+		for b in 0..=255u8 {
+			assert_eq!(ws.contains(b), b.is_ascii_whitespace());
+			assert_eq!(hex.contains(b), b.is_ascii_hexdigit());
+			assert_eq!(alnum.contains(b), b.is_ascii_alphanumeric());
+		}
+
+		let ident = ByteSet::new().alpha().byte(b'_')
+			.union(&ByteSet::new().digit());
+		assert_eq!(ident, alnum.byte(b'_'));
+
+	}
+
+}
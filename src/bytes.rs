@@ -0,0 +1,71 @@
+use crate::ParseIterator;
+
+/// `Iterator` over the bytes of a `ParseIterator`.
+///
+/// `ParseIterator` can't implement `Iterator` itself since both have a
+/// `next` method, which would make every call ambiguous.
+#[derive(Debug)]
+pub struct Bytes<'a, T> {
+	inner: &'a mut T
+}
+
+impl<'a, T> Bytes<'a, T> {
+	pub(super) fn new(inner: &'a mut T) -> Self {
+		Self {inner}
+	}
+}
+
+impl<'s, 'a, T> Iterator for Bytes<'a, T>
+where T: ParseIterator<'s> {
+
+	type Item = u8;
+
+	#[inline]
+	fn next(&mut self) -> Option<u8> {
+		self.inner.next()
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		// wrappers can return less than what remains in the slice
+		(0, Some(self.inner.remaining().len()))
+	}
+
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+	use crate::*;
+
+	#[test]
+	fn bytes() {
+
+		let mut parser = StrParser::new("a1b2c3");
+		let digits: Vec<_> = parser.bytes()
+			.filter(u8::is_ascii_digit)
+			.collect();
+		assert_eq!(digits, b"123");
+		assert!(parser.next().is_none());
+
+		let mut parser = StrParser::new("ab,cd");
+		let mut split = parser.split_on_byte(b',');
+		let seg = split.next().unwrap();
+		assert_eq!(seg.bytes().count(), 2);
+		let mut upper = vec![];
+		for b in split.next().unwrap().bytes() {
+			upper.push(b.to_ascii_uppercase());
+		}
+		assert_eq!(upper, b"CD");
+
+		// stops early without consuming more
+		let mut parser = Parser::new(b"abc");
+		assert_eq!(parser.bytes().next(), Some(b'a'));
+		assert_eq!(parser.bytes().size_hint(), (0, Some(2)));
+		assert_eq!(parser.next(), Some(b'b'));
+
+	}
+
+}
@@ -0,0 +1,125 @@
+
+use crate::ParseIterator;
+
+use core::ops::{Deref, DerefMut};
+
+
+/// Guard returned by `checkpoint`.
+///
+/// Derefs to the underlying iterator. When dropped the position from
+/// the creation of the checkpoint gets restored, unless `commit` was
+/// called.
+#[derive(Debug)]
+pub struct Checkpoint<'s, 'a, T>
+where T: ParseIterator<'s> {
+	inner: &'a mut T,
+	pit: Option<T::PointInTime>
+}
+
+impl<'s, 'a, T> Checkpoint<'s, 'a, T>
+where T: ParseIterator<'s> {
+	pub(super) fn new(inner: &'a mut T) -> Self {
+		let pit = Some(inner.pit());
+		Self { inner, pit }
+	}
+
+	/// Keeps the current position.
+	#[inline]
+	pub fn commit(mut self) {
+		self.pit = None;
+	}
+
+	/// Restores the position without dropping the checkpoint, so it can
+	/// be tried again.
+	#[inline]
+	pub fn restore(&mut self) {
+		if let Some(pit) = self.pit {
+			self.inner.restore_pit(pit);
+		}
+	}
+}
+
+impl<'s, 'a, T> Deref for Checkpoint<'s, 'a, T>
+where T: ParseIterator<'s> {
+	type Target = T;
+
+	fn deref(&self) -> &T {
+		self.inner
+	}
+}
+
+impl<'s, 'a, T> DerefMut for Checkpoint<'s, 'a, T>
+where T: ParseIterator<'s> {
+	fn deref_mut(&mut self) -> &mut T {
+		self.inner
+	}
+}
+
+impl<'s, 'a, T> Drop for Checkpoint<'s, 'a, T>
+where T: ParseIterator<'s> {
+	fn drop(&mut self) {
+		self.restore();
+	}
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+	use crate::*;
+
+	#[test]
+	fn restore_on_drop() {
+
+		let mut parser = StrParser::new("abc");
+		{
+			let mut cp = parser.checkpoint();
+			cp.consume_len(2).unwrap();
+			assert_eq!(cp.peek(), Some(b'c'));
+		}
+		assert_eq!(parser.offset(), 0);
+
+	}
+
+	#[test]
+	fn commit() {
+
+		let mut parser = StrParser::new("abc");
+		let mut cp = parser.checkpoint();
+		cp.consume_len(2).unwrap();
+		cp.commit();
+		assert_eq!(parser.next(), Some(b'c'));
+
+	}
+
+	#[test]
+	fn restore_and_retry() {
+
+		let mut parser = StrParser::new("ab");
+		let mut cp = parser.checkpoint();
+		assert!(cp.expect_byte(b'b').is_err());
+		cp.restore();
+		assert!(cp.expect_byte(b'a').is_ok());
+		cp.commit();
+		assert_eq!(parser.offset(), 1);
+
+	}
+
+	#[test]
+	fn nested() {
+
+		let mut parser = StrParser::new("abc");
+		let mut outer = parser.checkpoint();
+		outer.advance();
+		{
+			let mut inner = outer.checkpoint();
+			inner.advance();
+		}
+		assert_eq!(outer.next(), Some(b'b'));
+		outer.commit();
+		assert_eq!(parser.offset(), 2);
+
+	}
+
+}
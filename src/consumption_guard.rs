@@ -0,0 +1,90 @@
+use crate::ParseIterator;
+
+use core::ops::{Deref, DerefMut};
+use core::marker::PhantomData;
+
+
+/// Guard returned by `guard_full_consumption`.
+///
+/// Derefs to the underlying iterator. When dropped in a debug build
+/// it panics if the iterator was not fully consumed.
+#[derive(Debug)]
+pub struct FullConsumptionGuard<'s, 'a, T>
+where T: ParseIterator<'s> {
+	inner: &'a mut T,
+	marker: PhantomData<&'s [u8]>
+}
+
+impl<'s, 'a, T> FullConsumptionGuard<'s, 'a, T>
+where T: ParseIterator<'s> {
+	pub(super) fn new(inner: &'a mut T) -> Self {
+		Self {
+			inner,
+			marker: PhantomData
+		}
+	}
+}
+
+impl<'s, 'a, T> Deref for FullConsumptionGuard<'s, 'a, T>
+where T: ParseIterator<'s> {
+	type Target = T;
+
+	fn deref(&self) -> &T {
+		self.inner
+	}
+}
+
+impl<'s, 'a, T> DerefMut for FullConsumptionGuard<'s, 'a, T>
+where T: ParseIterator<'s> {
+	fn deref_mut(&mut self) -> &mut T {
+		self.inner
+	}
+}
+
+impl<'s, 'a, T> Drop for FullConsumptionGuard<'s, 'a, T>
+where T: ParseIterator<'s> {
+	fn drop(&mut self) {
+		// don't panic while panicking
+		#[cfg(feature = "std")]
+		let panicking = std::thread::panicking();
+		// without std there is no way to know
+		#[cfg(not(feature = "std"))]
+		let panicking = false;
+
+		if cfg!(debug_assertions) && !panicking {
+			debug_assert!(
+				self.inner.peek().is_none(),
+				"parser was not fully consumed"
+			);
+		}
+	}
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+	use crate::*;
+
+	#[test]
+	fn fully_consumed() {
+
+		let mut parser = StrParser::new("abc");
+		let mut guard = parser.guard_full_consumption();
+		guard.consume_len(3).unwrap();
+
+	}
+
+	#[cfg(debug_assertions)]
+	#[test]
+	#[should_panic(expected = "parser was not fully consumed")]
+	fn not_fully_consumed() {
+
+		let mut parser = StrParser::new("abc");
+		let mut guard = parser.guard_full_consumption();
+		guard.consume_len(2).unwrap();
+
+	}
+
+}
@@ -0,0 +1,140 @@
+//!
+//! Parse content enclosed in delimiters like `(..)` or `[..]`.
+//!
+//! ## Example
+//! ```
+//! # use byte_parser::{StrParser, ParseIterator, delimited::delimited_nested};
+//! let mut parser = StrParser::new("(a(b)c) rest");
+//! let inner = delimited_nested(&mut parser, b'(', b')').unwrap();
+//! assert_eq!(inner, b"a(b)c");
+//! assert_eq!(parser.next(), Some(b' '));
+//! ```
+
+use crate::ParseIterator;
+
+
+/// Expects `open`, then returns everything until the first `close` and
+/// consumes the `close`.
+///
+/// Returns `None` without advancing if the next byte is not `open` or if
+/// `close` is missing.
+///
+/// ## Example
+/// ```
+/// # use byte_parser::{StrParser, ParseIterator, delimited::delimited};
+/// let mut parser = StrParser::new("[key]=value");
+/// assert_eq!(delimited(&mut parser, b'[', b']'), Some(&b"key"[..]));
+/// assert_eq!(parser.next(), Some(b'='));
+/// ```
+pub fn delimited<'s, I>(iter: &mut I, open: u8, close: u8) -> Option<&'s [u8]>
+where I: ParseIterator<'s> {
+	delimited_with(iter, open, |b| b == close)
+}
+
+/// Like `delimited` but counts nested `open` and `close` bytes, so
+/// `(a(b)c)` returns `a(b)c`.
+///
+/// Returns `None` without advancing if the next byte is not `open` or if
+/// the matching `close` is missing.
+pub fn delimited_nested<'s, I>(
+	iter: &mut I,
+	open: u8,
+	close: u8
+) -> Option<&'s [u8]>
+where I: ParseIterator<'s> {
+	let mut depth = 0usize;
+	delimited_with(iter, open, |b| {
+		if b == open {
+			depth += 1;
+		} else if b == close {
+			if depth == 0 {
+				return true
+			}
+			depth -= 1;
+		}
+		false
+	})
+}
+
+// is_close gets called for every byte after open
+fn delimited_with<'s, I, F>(
+	iter: &mut I,
+	open: u8,
+	mut is_close: F
+) -> Option<&'s [u8]>
+where
+	I: ParseIterator<'s>,
+	F: FnMut(u8) -> bool {
+	let pit = iter.pit();
+	iter.next_if(|&b| b == open)?;
+
+	let start = iter.offset();
+	loop {
+		match iter.next() {
+			Some(b) if is_close(b) => break,
+			Some(_) => {},
+			None => {
+				iter.restore_pit(pit);
+				return None
+			}
+		}
+	}
+
+	// the close byte is not part of the content
+	let end = iter.offset() - 1;
+	Some(&iter.slice()[start..end])
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+	use super::*;
+	use crate::*;
+
+	#[test]
+	fn test_delimited() {
+
+		let mut parser = StrParser::new("(a(b)c)");
+		assert_eq!(delimited(&mut parser, b'(', b')'), Some(&b"a(b"[..]));
+		assert_eq!(parser.next(), Some(b'c'));
+
+		let mut parser = StrParser::new("()");
+		assert_eq!(delimited(&mut parser, b'(', b')'), Some(&b""[..]));
+		assert!(parser.next().is_none());
+
+		// same open and close byte
+		let mut parser = StrParser::new("|a|b");
+		assert_eq!(delimited(&mut parser, b'|', b'|'), Some(&b"a"[..]));
+
+	}
+
+	#[test]
+	fn test_delimited_nested() {
+
+		let mut parser = StrParser::new("{a{b{}}c}d");
+		let inner = delimited_nested(&mut parser, b'{', b'}');
+		assert_eq!(inner, Some(&b"a{b{}}c"[..]));
+		assert_eq!(parser.next(), Some(b'd'));
+
+	}
+
+	#[test]
+	fn missing_delimiters() {
+
+		let cases: [&[u8]; 4] = [b"a)", b"(a", b"(a(b)", b""];
+
+		for case in cases.iter() {
+			let mut parser = Parser::new(case);
+			assert!(delimited_nested(&mut parser, b'(', b')').is_none());
+			assert_eq!(parser.offset(), 0);
+		}
+
+		let mut parser = Parser::new(b"(a");
+		assert!(delimited(&mut parser, b'(', b')').is_none());
+		assert_eq!(parser.offset(), 0);
+
+	}
+
+}
@@ -0,0 +1,95 @@
+use core::fmt;
+
+
+/// Error returned by methods which need to report where parsing failed.
+///
+/// ## Example
+/// ```
+/// # use byte_parser::{StrParser, ParseIterator};
+/// let mut parser = StrParser::new("key value");
+/// parser.consume_while_byte_fn(u8::is_ascii_alphabetic);
+/// let err = parser.expect_byte_err(b'=', "equal sign").unwrap_err();
+/// assert_eq!(err.to_string(), "position 3: expected equal sign, found ' '");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseError {
+	position: usize,
+	found: Option<u8>,
+	expected: Option<&'static str>
+}
+
+impl ParseError {
+
+	pub(crate) fn new(position: usize, found: Option<u8>) -> Self {
+		Self {position, found, expected: None}
+	}
+
+	/// Sets a label describing what was expected.
+	pub fn with_expected(mut self, expected: &'static str) -> Self {
+		self.expected = Some(expected);
+		self
+	}
+
+	/// Returns the byte offset at which the error occured.
+	pub fn position(&self) -> usize {
+		self.position
+	}
+
+	/// Returns the byte that was found instead of the expected one.
+	/// `None` means the end was reached.
+	pub fn found(&self) -> Option<u8> {
+		self.found
+	}
+
+	/// Returns the label describing what was expected if one was set.
+	pub fn expected(&self) -> Option<&'static str> {
+		self.expected
+	}
+
+}
+
+impl fmt::Display for ParseError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "position {}: ", self.position)?;
+
+		match self.expected {
+			Some(expected) => write!(f, "expected {}, found ", expected)?,
+			None => f.write_str("unexpected ")?
+		}
+
+		match self.found {
+			Some(b) if b.is_ascii_graphic() || b == b' ' => {
+				write!(f, "'{}'", b as char)
+			},
+			Some(b) => write!(f, "0x{:02x}", b),
+			None => f.write_str("end of input")
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for ParseError {}
+
+
+#[cfg(test)]
+mod tests {
+
+	use super::*;
+
+	#[test]
+	fn display() {
+
+		let err = ParseError::new(4, Some(b'$'));
+		assert_eq!(err.to_string(), "position 4: unexpected '$'");
+
+		let err = ParseError::new(0, Some(0xff)).with_expected("digit");
+		assert_eq!(err.to_string(), "position 0: expected digit, found 0xff");
+
+		let err = ParseError::new(2, None).with_expected("`)`");
+		assert_eq!(err.expected(), Some("`)`"));
+		assert_eq!(err.to_string(), "position 2: expected `)`, found end of input");
+
+	}
+
+}
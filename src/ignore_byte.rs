@@ -1,8 +1,16 @@
 
 use crate::{
 	ParseIterator,
-	recorder::Recorder
+	recorder::Recorder,
+	byte_set::ByteSet
 };
+#[cfg(feature = "alloc")]
+use crate::byte_search::find_byte;
+
+#[cfg(feature = "alloc")]
+use alloc::{borrow::Cow, string::String};
+#[cfg(feature = "alloc")]
+use core::ops::{Deref, DerefMut};
 
 
 #[derive(Debug)]
@@ -46,6 +54,237 @@ where T: ParseIterator<'s> {
 		self.inner.recorder()
 	}
 
+	fn recorder_depth(&self) -> usize {
+		self.inner.recorder_depth()
+	}
+
+	fn rewind(&mut self, len: usize) -> Result<&mut Self, usize> {
+		self.inner.rewind(len)?;
+		Ok(self)
+	}
+
+	#[inline]
+	unsafe fn is_valid_utf8() -> bool {
+		T::is_valid_utf8()
+	}
+
+}
+
+
+/// `IgnoreByte` which can return the consumed bytes without the ignored
+/// byte with `consume_compacted` and `consume_compacted_str`.
+///
+/// Derefs to `IgnoreByte`, so every other method behaves the same and
+/// for example `to_slice` still contains the ignored byte.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Debug)]
+pub struct IgnoreByteCompacting<'a, T> {
+	inner: IgnoreByte<'a, T>
+}
+
+#[cfg(feature = "alloc")]
+impl<'s, 'a, T> IgnoreByteCompacting<'a, T>
+where T: ParseIterator<'s> {
+	pub(super) fn new(inner: &'a mut T, byte: u8) -> Self {
+		Self {inner: IgnoreByte::new(inner, byte)}
+	}
+
+	/// Consumes the iterator and returns the consumed bytes without the
+	/// ignored byte. Recording is not required.
+	///
+	/// Returns a borrowed slice if no ignored byte was consumed, else an
+	/// owned copy.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// # use std::borrow::Cow;
+	/// let mut parser = StrParser::new("1'000'000");
+	/// let num = parser.ignore_byte_compacting(b'\'').consume_compacted();
+	/// assert!(matches!(num, Cow::Owned(_)));
+	/// assert_eq!(num, &b"1000000"[..]);
+	///
+	/// let mut parser = StrParser::new("1000");
+	/// let num = parser.ignore_byte_compacting(b'\'').consume_compacted();
+	/// assert!(matches!(num, Cow::Borrowed(b"1000")));
+	/// ```
+	pub fn consume_compacted(&mut self) -> Cow<'s, [u8]> {
+		let byte = self.inner.byte;
+		let slice = self.inner.record().consume_to_slice();
+
+		match find_byte(byte, slice) {
+			Some(_) => Cow::Owned(
+				slice.iter()
+					.copied()
+					.filter(|&b| b != byte)
+					.collect()
+			),
+			None => Cow::Borrowed(slice)
+		}
+	}
+
+	/// Like `consume_compacted` but returns a `str`.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("a\r\nb");
+	/// let s = parser.ignore_byte_compacting(b'\r').consume_compacted_str();
+	/// assert_eq!(s, "a\nb");
+	/// ```
+	///
+	/// ## Panics
+	/// Panics if the bytes without the ignored byte are not valid utf8.
+	pub fn consume_compacted_str(&mut self) -> Cow<'s, str> {
+		// removing an ascii byte keeps utf8 valid
+		let valid = unsafe { T::is_valid_utf8() } && self.inner.byte.is_ascii();
+
+		match self.consume_compacted() {
+			Cow::Borrowed(s) if valid => Cow::Borrowed(
+				// safe because the data is valid utf8
+				unsafe { core::str::from_utf8_unchecked(s) }
+			),
+			Cow::Borrowed(s) => Cow::Borrowed(
+				core::str::from_utf8(s).expect("invalid utf8")
+			),
+			Cow::Owned(v) if valid => Cow::Owned(
+				// safe because the data is valid utf8
+				unsafe { String::from_utf8_unchecked(v) }
+			),
+			Cow::Owned(v) => Cow::Owned(
+				String::from_utf8(v).expect("invalid utf8")
+			)
+		}
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T> Deref for IgnoreByteCompacting<'a, T> {
+	type Target = IgnoreByte<'a, T>;
+
+	fn deref(&self) -> &IgnoreByte<'a, T> {
+		&self.inner
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T> DerefMut for IgnoreByteCompacting<'a, T> {
+	fn deref_mut(&mut self) -> &mut IgnoreByte<'a, T> {
+		&mut self.inner
+	}
+}
+
+
+#[derive(Debug)]
+pub struct IgnoreBytes<'a, T> {
+	inner: &'a mut T,
+	bytes: ByteSet
+}
+
+impl<'a, T> IgnoreBytes<'a, T> {
+	pub(super) fn new(inner: &'a mut T, bytes: &[u8]) -> Self {
+		Self {inner, bytes: ByteSet::from_slice(bytes)}
+	}
+}
+
+impl<'s, 'a, T> ParseIterator<'s> for IgnoreBytes<'a, T>
+where T: ParseIterator<'s> {
+
+	type PointInTime = T::PointInTime;
+
+	fn slice(&self) -> &'s [u8] {
+		self.inner.slice()
+	}
+
+	fn pit(&self) -> Self::PointInTime {
+		self.inner.pit()
+	}
+
+	fn restore_pit(&mut self, pit: Self::PointInTime) {
+		self.inner.restore_pit( pit )
+	}
+
+	fn advance(&mut self) -> Option<()> {
+		let bytes = &self.bytes;
+		self.inner
+			.while_byte_fn(|&b| bytes.contains(b))
+			.consume();// consume
+		self.inner.advance()
+	}
+
+	fn recorder(&self) -> Option<&Recorder> {
+		self.inner.recorder()
+	}
+
+	fn recorder_depth(&self) -> usize {
+		self.inner.recorder_depth()
+	}
+
+	fn rewind(&mut self, len: usize) -> Result<&mut Self, usize> {
+		self.inner.rewind(len)?;
+		Ok(self)
+	}
+
+	#[inline]
+	unsafe fn is_valid_utf8() -> bool {
+		T::is_valid_utf8()
+	}
+
+}
+
+
+#[derive(Debug)]
+pub struct IgnoreByteFn<'a, T, F> {
+	inner: &'a mut T,
+	f: F
+}
+
+impl<'a, T, F> IgnoreByteFn<'a, T, F> {
+	pub(super) fn new(inner: &'a mut T, f: F) -> Self {
+		Self {inner, f}
+	}
+}
+
+impl<'s, 'a, T, F> ParseIterator<'s> for IgnoreByteFn<'a, T, F>
+where
+	T: ParseIterator<'s>,
+	F: Fn(&u8) -> bool {
+
+	type PointInTime = T::PointInTime;
+
+	fn slice(&self) -> &'s [u8] {
+		self.inner.slice()
+	}
+
+	fn pit(&self) -> Self::PointInTime {
+		self.inner.pit()
+	}
+
+	fn restore_pit(&mut self, pit: Self::PointInTime) {
+		self.inner.restore_pit( pit )
+	}
+
+	fn advance(&mut self) -> Option<()> {
+		self.inner
+			.while_byte_fn(&self.f)
+			.consume();// consume
+		self.inner.advance()
+	}
+
+	fn recorder(&self) -> Option<&Recorder> {
+		self.inner.recorder()
+	}
+
+	fn recorder_depth(&self) -> usize {
+		self.inner.recorder_depth()
+	}
+
+	fn rewind(&mut self, len: usize) -> Result<&mut Self, usize> {
+		self.inner.rewind(len)?;
+		Ok(self)
+	}
+
 	#[inline]
 	unsafe fn is_valid_utf8() -> bool {
 		T::is_valid_utf8()
@@ -79,4 +318,73 @@ mod tests {
 
 	}
 
-}
\ No newline at end of file
+	#[test]
+	fn ignore_bytes() {
+
+		let mut parser = StrParser::new("a\r\nb\nc");
+		let mut parser = parser.ignore_bytes(b"\r\n");
+		assert_eq!( Some(b'a'), parser.next() );
+		assert_eq!( Some(b'b'), parser.next() );
+		assert_eq!( Some(b'c'), parser.next() );
+		assert!( parser.next().is_none() );
+
+	}
+
+	#[test]
+	fn ignore_byte_fn_with_split() {
+
+		let mut parser = StrParser::new("a b,\tc d ,e");
+		let parts: Vec<_> = parser
+			.ignore_byte_fn(u8::is_ascii_whitespace)
+			.split_on_byte(b',')
+			.map_and_collect(|p| {
+				let mut s = String::new();
+				while let Some(b) = p.next() {
+					s.push(b as char);
+				}
+				s
+			});
+		assert_eq!(parts, ["ab", "cd", "e"]);
+
+		// skipped bytes are still recorded
+		let mut parser = StrParser::new("a b");
+		let s = parser
+			.ignore_byte_fn(u8::is_ascii_whitespace)
+			.record()
+			.consume_to_str();
+		assert_eq!(s, "a b");
+
+	}
+
+	#[cfg(feature = "alloc")]
+	#[test]
+	fn ignore_byte_compacting() {
+
+		use std::borrow::Cow;
+
+		let mut parser = StrParser::new("a\r\nb\r");
+		let s = parser.ignore_byte_compacting(b'\r').consume_compacted_str();
+		assert!(matches!(&s, Cow::Owned(s) if s == "a\nb"));
+		assert!(parser.next().is_none());
+
+		// zero copy if the byte is not present
+		let mut parser = StrParser::new("ab,cd");
+		let mut split = parser.split_on_byte(b',');
+		let s = split.next().unwrap()
+			.ignore_byte_compacting(b'\r')
+			.consume_compacted_str();
+		assert!(matches!(s, Cow::Borrowed("ab")));
+
+		// next skips the byte like ignore_byte
+		let mut parser = Parser::new(b"\ra\rb");
+		let mut parser = parser.ignore_byte_compacting(b'\r');
+		assert_eq!(parser.next(), Some(b'a'));
+		assert_eq!(parser.consume_compacted(), &b"b"[..]);
+
+		let mut parser = StrParser::new("");
+		let s = parser.ignore_byte_compacting(b'\r').consume_compacted_str();
+		assert!(matches!(s, Cow::Borrowed("")));
+
+	}
+
+}
@@ -0,0 +1,157 @@
+//!
+//! Iterate over `KEY=VALUE` like lines or `a=1;b=2` like pairs.
+//!
+//! ## Example
+//! ```
+//! # use byte_parser::{StrParser, ParseIterator};
+//! let mut parser = StrParser::new("\
+//! 	## a comment\r\n\
+//! 	KEY = value\r\n\
+//! 	\r\n\
+//! 	OTHER=more=values\n\
+//! 	FLAG\
+//! ");
+//!
+//! let pairs: Vec<_> = parser.parse_kv_lines(b'=').collect();
+//! assert_eq!(pairs, [
+//! 	("KEY", "value"),
+//! 	("OTHER", "more=values"),
+//! 	("FLAG", "")
+//! ]);
+//! ```
+
+use crate::{
+	ParseIterator,
+	split_on_byte::SplitOnByte
+};
+
+use core::marker::PhantomData;
+
+
+/// Iterator returned by `parse_kv_lines` and `split_key_value`.
+///
+/// Yields the trimmed key and value of every entry. Entries with an empty
+/// key (which includes blank lines) are skipped, with `parse_kv_lines` also
+/// lines starting with a `#`. An entry without the separator is returned
+/// as a key with an empty value.
+///
+/// ## Panics
+/// Panics if a line contains invalid utf8.
+#[derive(Debug)]
+pub struct KeyValues<'s, 'a, T> {
+	entries: SplitOnByte<'a, T>,
+	kv_sep: u8,
+	comments: bool,
+	marker: PhantomData<&'s str>
+}
+
+impl<'s, 'a, T> KeyValues<'s, 'a, T>
+where T: ParseIterator<'s> {
+	pub(super) fn lines(inner: &'a mut T, kv_sep: u8) -> Self {
+		Self {
+			entries: SplitOnByte::new(inner, b'\n'),
+			kv_sep,
+			comments: true,
+			marker: PhantomData
+		}
+	}
+
+	pub(super) fn pairs(inner: &'a mut T, pair_sep: u8, kv_sep: u8) -> Self {
+		Self {
+			entries: SplitOnByte::new(inner, pair_sep),
+			kv_sep,
+			comments: false,
+			marker: PhantomData
+		}
+	}
+}
+
+impl<'s, 'a, T> Iterator for KeyValues<'s, 'a, T>
+where T: ParseIterator<'s> {
+
+	type Item = (&'s str, &'s str);
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let kv_sep = self.kv_sep;
+
+		loop {
+			let line = self.entries.next()?;
+
+			let key = line
+				.record()
+				.consume_while_byte_fn(|&b| b != kv_sep)
+				.to_str()
+				.trim();
+
+			// skip blank lines and comments
+			if key.is_empty() || (self.comments && key.starts_with('#')) {
+				continue
+			}
+
+			let has_sep = line.advance().is_some();
+			if !has_sep {
+				return Some((key, ""))
+			}
+
+			// trim also removes a possible \r
+			let value = line
+				.record()
+				.consume_to_str()
+				.trim();
+
+			return Some((key, value))
+		}
+	}
+
+}
+
+
+#[cfg(test)]
+mod tests {
+
+	use crate::*;
+
+	#[test]
+	fn kv_lines() {
+
+		let mut parser = StrParser::new("\
+			a=1\r\n\
+			#b=2\n\
+			\n\
+			  c  =  3  \r\n\
+			d\r\n\
+			=5\n\
+		");
+
+		let pairs: Vec<_> = parser.parse_kv_lines(b'=').collect();
+		assert_eq!(pairs, [("a", "1"), ("c", "3"), ("d", "")]);
+		assert!(parser.next().is_none());
+
+	}
+
+	#[test]
+	fn pairs() {
+
+		let mut parser = StrParser::new("a=1; b = 2 ;;#c=3;d;e=f=g;");
+
+		let pairs: Vec<_> = parser.split_key_value(b';', b'=').collect();
+		assert_eq!(pairs, [
+			("a", "1"),
+			("b", "2"),
+			("#c", "3"),
+			("d", ""),
+			("e", "f=g")
+		]);
+		assert!(parser.next().is_none());
+
+	}
+
+	#[test]
+	fn kv_lines_empty() {
+
+		let mut parser = StrParser::new("");
+		assert!(parser.parse_kv_lines(b'=').next().is_none());
+
+	}
+
+}
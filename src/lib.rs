@@ -119,17 +119,59 @@
 //! assert!(".42".parse::<Number>().is_err());
 //! assert!("5.42 ".parse::<Number>().is_err());
 //! ```
+//!
+//! ## Features
+//! - `std` (default): implements `std::error::Error` for `ParseError` and
+//!   enables the `read_parser` module.
+//! - `alloc`: enables the `tokenizer`, `utf16` and `quoted` modules.
+//!
+//! Without `std` the crate is `no_std`.
 
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 pub mod position;
 mod parse_iterator;
 mod expect_byte;
+mod byte_search;
+pub mod error;
 pub mod ignore_byte;
+pub mod normalize_crlf;
 pub mod while_byte_fn;
+pub mod while_char_fn;
 pub mod split_on_byte;
+pub mod lines;
+pub mod split_on_slice;
+pub mod split_on_any_byte;
+pub mod split_on_fn;
+pub mod split_respecting;
+pub mod rsplit_on_byte;
+pub mod key_value;
+pub mod delimited;
 pub mod recorder;
 pub mod stop;
+pub mod take;
+pub mod bytes;
+pub mod positions;
+pub mod consumption_guard;
+pub mod checkpoint;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod utf16;
+pub mod byte_set;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod tokenizer;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod quoted;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub mod read_parser;
+pub mod numbers;
 pub mod pit;
 #[cfg(feature = "unstable-parse-iter")]
 #[cfg_attr(docsrs, doc(cfg(feature = "unstable-parse-iter")))]
@@ -137,12 +179,27 @@ pub mod parse_iter;
 
 pub use parse_iterator::ParseIterator;
 pub use expect_byte::ExpectByte;
+pub use error::ParseError;
 use recorder::Recorder;
 use position::Position;
-use pit::ParserPointInTime;
+use pit::{ParserPointInTime, TrackedPointInTime};
 
 /// `ParseIterator` implementation for a slice.
-#[derive(Debug)]
+///
+/// Cloning is the recommended way to create an independent cursor
+/// over the same data.
+///
+/// Comparing a `Parser` with a slice or str compares the remaining bytes,
+/// not the whole slice.
+///
+/// ## Example
+/// ```
+/// # use byte_parser::{Parser, ParseIterator};
+/// let mut parser = Parser::new(b"key=value");
+/// parser.consume_while_byte_fn(|&b| b != b'=');
+/// assert_eq!(parser, b"=value");
+/// ```
+#[derive(Debug, Clone)]
 pub struct Parser<'s> {
 	slice: &'s [u8],
 	pit: ParserPointInTime
@@ -160,6 +217,63 @@ impl<'s> Parser<'s> {
 		}
 	}
 
+	/// Sets the position so that the next byte returned is at `pos`.
+	/// Returns `None` if `pos` is after the end of the slice.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{Parser, ParseIterator};
+	/// let mut parser = Parser::new(b"\x02abcd");
+	/// let offset = parser.next().unwrap() as usize;
+	/// assert_eq!(parser.seek(offset).unwrap().next(), Some(b'b'));
+	/// assert!(parser.seek(6).is_none());
+	/// ```
+	pub fn seek(&mut self, pos: usize) -> Option<&mut Self> {
+		if pos > self.slice.len() {
+			return None
+		}
+
+		self.pit = ParserPointInTime::at(pos);
+		Some(self)
+	}
+
+	/// Moves back to the start of the slice, useful for parsing the
+	/// same data twice.
+	///
+	/// Adapters like `split_on_byte` borrow the parser, so they need to
+	/// be dropped before the parser can be reset.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{Parser, ParseIterator};
+	/// let mut parser = Parser::new(b"ab");
+	/// parser.consume();
+	/// assert_eq!(parser.reset().next(), Some(b'a'));
+	/// ```
+	pub fn reset(&mut self) -> &mut Self {
+		self.pit = ParserPointInTime::new();
+		self
+	}
+
+	/// Converts this `Parser` into a `StrParser` at the same position.
+	/// Returns an error if the whole slice is not valid utf8.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{Parser, ParseIterator};
+	/// let mut parser = Parser::new(b"\x02hi");
+	/// let len = parser.next().unwrap() as usize;
+	/// let mut parser = parser.into_str_parser().unwrap();
+	/// assert_eq!(parser.record().consume_len(len).unwrap().to_str(), "hi");
+	/// ```
+	pub fn into_str_parser(self) -> Result<StrParser<'s>, core::str::Utf8Error> {
+		let inner = core::str::from_utf8(self.slice)?;
+		Ok(StrParser {
+			inner,
+			pit: self.pit
+		})
+	}
+
 }
 
 impl<'s> ParseIterator<'s> for Parser<'s> {
@@ -189,6 +303,20 @@ impl<'s> ParseIterator<'s> for Parser<'s> {
 		}
 	}
 
+	fn advance_n(&mut self, n: usize) -> Option<usize> {
+		if n == 0 {
+			return Some(0)
+		}
+
+		let n = n.min(self.slice.len() - self.offset());
+		if n == 0 {
+			return None
+		}
+
+		self.pit.pos = (self.pit.pos + n).into();
+		Some(n)
+	}
+
 	fn recorder(&self) -> Option<&Recorder> {
 		None
 	}
@@ -198,11 +326,22 @@ impl<'s> ParseIterator<'s> for Parser<'s> {
 		false
 	}
 
+	#[inline]
+	unsafe fn advances_by_one() -> bool {
+		true
+	}
+
 }
 
 
 /// `ParseIterator` implementation for a str.
-#[derive(Debug)]
+///
+/// Cloning is the recommended way to create an independent cursor
+/// over the same data.
+///
+/// Comparing a `StrParser` with a slice or str compares the remaining
+/// bytes, not the whole str.
+#[derive(Debug, Clone)]
 pub struct StrParser<'s> {
 	inner: &'s str,
 	pit: ParserPointInTime
@@ -218,6 +357,63 @@ impl<'s> StrParser<'s> {
 		}
 	}
 
+	/// Sets the position so that the next byte returned is at `pos`.
+	/// Returns `None` if `pos` is after the end of the str or if it is not
+	/// on a char boundary.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("äb");
+	/// // the ä is two bytes long
+	/// assert!(parser.seek(1).is_none());
+	/// let s = parser.seek(2).unwrap()
+	/// 	.record()
+	/// 	.consume_to_str();
+	/// assert_eq!(s, "b");
+	/// ```
+	pub fn seek(&mut self, pos: usize) -> Option<&mut Self> {
+		// also checks that pos is not after the end
+		if !self.inner.is_char_boundary(pos) {
+			return None
+		}
+
+		self.pit = ParserPointInTime::at(pos);
+		Some(self)
+	}
+
+	/// Moves back to the start of the str, useful for parsing the
+	/// same data twice.
+	///
+	/// Adapters like `split_on_byte` borrow the parser, so they need to
+	/// be dropped before the parser can be reset.
+	pub fn reset(&mut self) -> &mut Self {
+		self.pit = ParserPointInTime::new();
+		self
+	}
+
+	/// Skips a utf8 byte order mark (`EF BB BF`) if the parser is at the
+	/// start and the str begins with one. Returns `true` if it was skipped.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("\u{FEFF}a,b");
+	/// assert!(parser.skip_bom());
+	/// assert_eq!(parser.remaining_str(), "a,b");
+	/// assert!(!parser.skip_bom());
+	/// ```
+	pub fn skip_bom(&mut self) -> bool {
+		let has_bom = self.offset() == 0
+			&& self.inner.as_bytes().starts_with(b"\xEF\xBB\xBF");
+
+		if has_bom {
+			self.pit = ParserPointInTime::at(3);
+		}
+
+		has_bom
+	}
+
 }
 
 impl<'s> ParseIterator<'s> for StrParser<'s> {
@@ -247,10 +443,145 @@ impl<'s> ParseIterator<'s> for StrParser<'s> {
 		}
 	}
 
+	fn advance_n(&mut self, n: usize) -> Option<usize> {
+		if n == 0 {
+			return Some(0)
+		}
+
+		let n = n.min(self.inner.len() - self.offset());
+		if n == 0 {
+			return None
+		}
+
+		self.pit.pos = (self.pit.pos + n).into();
+		Some(n)
+	}
+
+	fn recorder(&self) -> Option<&Recorder> {
+		None
+	}
+
+	#[inline]
+	unsafe fn is_valid_utf8() -> bool {
+		true
+	}
+
+	#[inline]
+	unsafe fn advances_by_one() -> bool {
+		true
+	}
+
+}
+
+/// `ParseIterator` implementation for a str which keeps track of the
+/// line and column.
+///
+/// Like `StrParser`, comparing with a slice or str compares the remaining
+/// bytes.
+///
+/// ## Example
+/// ```
+/// # use byte_parser::{TrackedStrParser, ParseIterator};
+/// let mut parser = TrackedStrParser::new("key\n  value");
+/// parser.consume_while_byte_fn(|&b| b != b'v');
+/// assert_eq!(parser.line(), 2);
+/// assert_eq!(parser.col(), 3);
+/// ```
+#[derive(Debug, Clone)]
+pub struct TrackedStrParser<'s> {
+	inner: &'s str,
+	pit: TrackedPointInTime
+}
+
+impl<'s> TrackedStrParser<'s> {
+
+	/// Creates a new `TrackedStrParser` from a str.
+	pub fn new(inner: &'s str) -> Self {
+		Self {
+			inner,
+			pit: TrackedPointInTime::new()
+		}
+	}
+
+	/// Returns the line of the next byte, starting at 1.
+	pub fn line(&self) -> usize {
+		self.pit.line()
+	}
+
+	/// Returns the column of the next byte, starting at 1.
+	/// The column is counted in bytes.
+	pub fn col(&self) -> usize {
+		self.pit.col()
+	}
+
+	/// Moves back to the start of the str, resetting the line and
+	/// column.
+	///
+	/// Adapters like `split_on_byte` borrow the parser, so they need to
+	/// be dropped before the parser can be reset.
+	pub fn reset(&mut self) -> &mut Self {
+		self.pit = TrackedPointInTime::new();
+		self
+	}
+
+}
+
+impl<'s> ParseIterator<'s> for TrackedStrParser<'s> {
+
+	type PointInTime = TrackedPointInTime;
+
+	fn slice(&self) -> &'s [u8] {
+		self.inner.as_bytes()
+	}
+
+	fn pit(&self) -> Self::PointInTime {
+		self.pit
+	}
+
+	fn restore_pit(&mut self, pit: Self::PointInTime) {
+		self.pit = pit;
+	}
+
+	fn advance(&mut self) -> Option<()> {
+		let n = self.pit.pos + 1;
+
+		let b = *self.inner.as_bytes().get(n)?;
+		self.pit.pos = n.into();
+
+		if b == b'\n' {
+			self.pit.line += 1;
+			self.pit.col = 1;
+		} else {
+			self.pit.col += 1;
+		}
+
+		Some(())
+	}
+
 	fn recorder(&self) -> Option<&Recorder> {
 		None
 	}
 
+	/// Updates the line and column.
+	fn rewind(&mut self, len: usize) -> Result<&mut Self, usize> {
+		let end = self.pit.pos + 1;
+		let pos = self.pit.pos.checked_sub(len).ok_or(end)?;
+		let start = pos + 1;
+
+		let bytes = self.inner.as_bytes();
+		self.pit.line -= bytes[start..end].iter()
+			.filter(|&&b| b == b'\n')
+			.count();
+		// the column is counted from the previous new line
+		self.pit.col = match bytes[..start].iter().rposition(|&b| b == b'\n') {
+			Some(nl) => start - nl,
+			None => start + 1
+		};
+		self.pit.pos = pos;
+
+		Ok(self)
+	}
+
 	#[inline]
 	unsafe fn is_valid_utf8() -> bool {
 		true
@@ -258,6 +589,43 @@ impl<'s> ParseIterator<'s> for StrParser<'s> {
 
 }
 
+// compares the remaining bytes (the ones which were not consumed yet)
+macro_rules! remaining_eq {
+	($($parser:ident),*) => {$(
+		impl<'s> PartialEq<[u8]> for $parser<'s> {
+			fn eq(&self, other: &[u8]) -> bool {
+				self.remaining() == other
+			}
+		}
+
+		impl<'s, 'o> PartialEq<&'o [u8]> for $parser<'s> {
+			fn eq(&self, other: &&'o [u8]) -> bool {
+				self.remaining() == *other
+			}
+		}
+
+		impl<'s, 'o, const N: usize> PartialEq<&'o [u8; N]> for $parser<'s> {
+			fn eq(&self, other: &&'o [u8; N]) -> bool {
+				self.remaining() == &other[..]
+			}
+		}
+
+		impl<'s> PartialEq<str> for $parser<'s> {
+			fn eq(&self, other: &str) -> bool {
+				self.remaining() == other.as_bytes()
+			}
+		}
+
+		impl<'s, 'o> PartialEq<&'o str> for $parser<'s> {
+			fn eq(&self, other: &&'o str) -> bool {
+				self.remaining() == other.as_bytes()
+			}
+		}
+	)*}
+}
+
+remaining_eq!(Parser, StrParser, TrackedStrParser);
+
 #[cfg(feature = "unstable-parse-iter")]
 #[cfg_attr(docsrs, doc(cfg(feature = "unstable-parse-iter")))]
 /// From a `ParseIterator` generate an `Iterator`.
@@ -354,6 +722,199 @@ mod tests {
 
 	}
 
+	#[test]
+	fn tracked_str_parser() {
+
+		let mut parser = TrackedStrParser::new("ab\ncd\n\nef");
+		assert_eq!((parser.line(), parser.col()), (1, 1));
+
+		parser.consume_len(2).unwrap();
+		assert_eq!((parser.line(), parser.col()), (1, 3));
+
+		// consume the newline
+		assert_eq!(parser.next(), Some(b'\n'));
+		assert_eq!((parser.line(), parser.col()), (2, 1));
+
+		parser.consume();
+		assert_eq!((parser.line(), parser.col()), (4, 3));
+
+	}
+
+	#[test]
+	fn into_str_parser() {
+
+		let mut parser = Parser::new("ab😀".as_bytes());
+		parser.consume_len(2).unwrap();
+		let mut parser = parser.into_str_parser().unwrap();
+		assert_eq!(parser.offset(), 2);
+		assert_eq!(parser.next_char(), Some('😀'));
+
+		let parser = Parser::new(b"a\xffb");
+		assert!(parser.into_str_parser().is_err());
+
+	}
+
+	#[test]
+	fn clone_parser() {
+
+		let mut parser = StrParser::new("abc");
+		parser.next();
+
+		let mut cloned = parser.clone();
+		assert_eq!(cloned.next(), Some(b'b'));
+		assert_eq!(cloned.next(), Some(b'c'));
+		assert!(cloned.next().is_none());
+
+		assert_eq!(parser.record().consume_to_str(), "bc");
+
+		let mut parser = Parser::new(b"ab");
+		let mut cloned = parser.clone();
+		cloned.consume();
+		assert_eq!(parser.next(), Some(b'a'));
+
+	}
+
+	#[test]
+	fn remaining_eq() {
+
+		let mut parser = StrParser::new("abc");
+		assert_eq!(parser, "abc");
+		parser.next();
+		assert_eq!(parser, "bc");
+		assert_eq!(parser, b"bc");
+		assert_eq!(parser, &b"bc"[..]);
+		assert!(parser != "abc");
+		assert!(parser == *"bc");
+		assert!(parser == b"bc"[..]);
+
+		let mut parser = TrackedStrParser::new("a\nb");
+		parser.consume_len(2).unwrap();
+		assert_eq!(parser, "b");
+		parser.consume();
+		assert_eq!(parser, "");
+
+		let parser = Parser::new(b"\xff");
+		assert_eq!(parser, b"\xff");
+
+	}
+
+	#[test]
+	fn reset() {
+
+		let mut parser = StrParser::new("a,bb,ccc");
+
+		// first pass counts the fields
+		let count = parser.count_bytes(b",") + 1;
+		assert_eq!(count, 3);
+		assert!(parser.next().is_none());
+
+		// second pass extracts them
+		let mut fields = Vec::with_capacity(count);
+		let mut split = parser.reset().split_on_byte(b',');
+		while let Some(field) = split.next() {
+			fields.push(field.record().consume_to_str());
+		}
+		assert_eq!(fields, ["a", "bb", "ccc"]);
+
+		let mut parser = TrackedStrParser::new("a\nb");
+		parser.consume();
+		assert_eq!(parser.line(), 2);
+		parser.reset();
+		assert_eq!((parser.line(), parser.col()), (1, 1));
+		assert_eq!(parser.next(), Some(b'a'));
+
+	}
+
+	#[test]
+	fn skip_bom() {
+
+		let mut parser = StrParser::new("\u{FEFF}\u{FEFF}");
+		assert!(parser.skip_bom());
+		assert_eq!(parser.offset(), 3);
+		// only at the start
+		assert!(!parser.skip_bom());
+		assert_eq!(parser.record().consume_to_str(), "\u{FEFF}");
+
+		let mut parser = StrParser::new("a\u{FEFF}");
+		assert!(!parser.skip_bom());
+		assert_eq!(parser.offset(), 0);
+
+		assert!(!StrParser::new("").skip_bom());
+		assert!(!StrParser::new("\u{FFFE}").skip_bom());
+
+	}
+
+	#[test]
+	fn seek() {
+
+		let mut parser = Parser::new(b"abc");
+		parser.consume();
+		assert_eq!(parser.seek(0).unwrap().next(), Some(b'a'));
+		assert!(parser.seek(3).unwrap().next().is_none());
+		assert!(parser.seek(4).is_none());
+		// a failed seek does not move
+		assert!(parser.next().is_none());
+
+		let mut parser = StrParser::new("a😀");
+		for pos in 2..5 {
+			assert!(parser.seek(pos).is_none());
+		}
+		assert!(parser.seek(6).is_none());
+		assert_eq!(parser.seek(1).unwrap().record().consume_to_str(), "😀");
+		assert!(parser.seek(5).unwrap().is_empty());
+
+	}
+
+	#[test]
+	fn tracked_str_parser_rewind() {
+
+		let mut parser = TrackedStrParser::new("ab\ncd\ne");
+		parser.consume();
+		assert_eq!((parser.line(), parser.col()), (3, 2));
+
+		parser.rewind(1).unwrap();
+		assert_eq!((parser.line(), parser.col()), (3, 1));
+		parser.rewind(2).unwrap();
+		assert_eq!((parser.line(), parser.col()), (2, 2));
+		parser.rewind(3).unwrap();
+		assert_eq!((parser.line(), parser.col()), (1, 2));
+		assert_eq!(parser.rewind(2).unwrap_err(), 1);
+		parser.rewind(1).unwrap();
+		assert_eq!((parser.line(), parser.col()), (1, 1));
+
+		assert_eq!(parser.record().consume_to_str(), "ab\ncd\ne");
+
+	}
+
+	#[test]
+	fn tracked_str_parser_backtrack() {
+
+		let mut parser = TrackedStrParser::new("a\nb\nc");
+		let pit = parser.pit();
+
+		// backtrack over a newline
+		parser.consume_len(3).unwrap();
+		assert_eq!((parser.line(), parser.col()), (2, 2));
+		parser.restore_pit(pit);
+		assert_eq!((parser.line(), parser.col()), (1, 1));
+
+		// a failing while_byte_fn restores the newline
+		parser.consume_len(1).unwrap();
+		let mut while_fn = parser.while_byte_fn(|&b| b == b'x');
+		assert!(while_fn.next().is_none());
+		assert_eq!((parser.line(), parser.col()), (1, 2));
+
+		// peek over the newline
+		assert_eq!(parser.peek(), Some(b'\n'));
+		assert_eq!((parser.line(), parser.col()), (1, 2));
+
+		// record across lines
+		let s = parser.record().consume_to_str();
+		assert_eq!(s, "\nb\nc");
+		assert_eq!((parser.line(), parser.col()), (3, 2));
+
+	}
+
 	#[test]
 	fn create_an_iterator() {
 		fn make_iter(s: &str) -> impl Iterator<Item=&str> {
@@ -387,4 +948,18 @@ mod tests {
 		assert!(iter.next().is_none());
 	}
 
+	#[cfg(feature = "unstable-parse-iter")]
+	#[test]
+	fn test_into_iter_with() {
+		let mut iter = StrParser::new("my str")
+			.into_iter_with(|parser| {
+				let mut split = parser.split_on_byte(b' ');
+				split.next()
+					.map(|n| n.record().consume_to_str())
+			});
+		assert_eq!(iter.next().unwrap(), "my");
+		assert_eq!(iter.next().unwrap(), "str");
+		assert!(iter.next().is_none());
+	}
+
 }
\ No newline at end of file
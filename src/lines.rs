@@ -0,0 +1,131 @@
+//!
+//! Iterate over lines ending with `\n` or `\r\n`.
+//!
+//! ## Example
+//! ```
+//! # use byte_parser::{StrParser, ParseIterator};
+//! let mut parser = StrParser::new("first\r\nsecond\n\nlast\n");
+//! let lines: Vec<_> = parser
+//! 	.lines()
+//! 	.map_and_collect(|line| line.record().consume_to_str());
+//!
+//! assert_eq!(lines, ["first", "second", "", "last"]);
+//! ```
+
+use crate::{
+	ParseIterator,
+	split_on_byte::{SplitOnByte, SplitOnByteIter}
+};
+
+use core::iter;
+
+
+/// Splitter returned by `lines`.
+///
+/// Works like `split_on_byte(b'\n')` but a `\r` directly before the `\n`
+/// is not part of the line and an empty last line is not returned, like
+/// `str::lines`.
+#[derive(Debug)]
+pub struct Lines<'a, T> {
+	inner: SplitOnByte<'a, T>
+}
+
+impl<'s, 'a, T> Lines<'a, T>
+where T: ParseIterator<'s> {
+	pub(super) fn new(inner: &'a mut T) -> Self {
+		Self {
+			inner: SplitOnByte::lines(inner)
+		}
+	}
+}
+
+impl<'s, 'a, T> Lines<'a, T>
+where T: ParseIterator<'s> {
+
+	// next
+	// can't implement Iterator since the segment borrows self
+	#[allow(clippy::should_implement_trait)]
+	pub fn next(&mut self) -> Option<&mut SplitOnByteIter<'a, T>> {
+		let line = self.inner.next()?;
+
+		// the input ended with a newline
+		if line.is_at_end() {
+			return None
+		}
+
+		Some(line)
+	}
+
+	// for_each
+	pub fn for_each<F>(&mut self, mut f: F) -> &mut Self
+	where F: FnMut(&mut SplitOnByteIter<'a, T>) {
+		while let Some(line) = self.next() {
+			f(line);
+		}
+
+		self
+	}
+
+	// map
+	pub fn map_and_collect<F, A, B>(&mut self, mut f: F) -> B
+	where
+		F: FnMut(&mut SplitOnByteIter<'a, T>) -> A,
+		B: iter::FromIterator<A> {
+		iter::from_fn(|| {
+			Some(f(self.next()?))
+		})
+		.collect()
+	}
+
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+	use crate::*;
+
+	fn lines(s: &str) -> Vec<&str> {
+		StrParser::new(s)
+			.lines()
+			.map_and_collect(|l| l.record().consume_to_str())
+	}
+
+	#[test]
+	fn same_as_str_lines() {
+
+		let long = "a".repeat(20) + "\r\n" + &"b".repeat(17);
+		let cases = [
+			"", "\n", "\r\n", "a", "a\n", "a\r\n", "a\n\n", "\n\na",
+			"a\r\nb\nc", "a\rb\n", "\r\r\n", "a\n\r\n", long.as_str()
+		];
+
+		for s in cases.iter() {
+			let expected: Vec<_> = s.lines().collect();
+			assert_eq!(lines(s), expected, "{:?}", s);
+		}
+
+	}
+
+	#[test]
+	fn partially_consumed() {
+
+		// IgnoreByte has no fast path
+		let mut parser = StrParser::new("ab\r\ncd\r\n");
+		let mut parser = parser.ignore_byte(0);
+		let mut lines = parser.lines();
+
+		let ab = lines.next().unwrap();
+		assert_eq!(ab.next(), Some(b'a'));
+		assert_eq!(ab.record().consume_to_str(), "b");
+		// the \r\n can't be rewound into
+		assert_eq!(ab.rewind(3).unwrap_err(), 2);
+
+		let cd = lines.next().unwrap();
+		assert_eq!(cd.record().consume_to_str(), "cd");
+		assert!(lines.next().is_none());
+
+	}
+
+}
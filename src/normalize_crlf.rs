@@ -0,0 +1,130 @@
+use crate::{
+	ParseIterator,
+	recorder::Recorder
+};
+
+
+#[derive(Debug)]
+pub struct NormalizeCrlf<'a, T> {
+	inner: &'a mut T
+}
+
+impl<'a, T> NormalizeCrlf<'a, T> {
+	pub(super) fn new(inner: &'a mut T) -> Self {
+		Self {inner}
+	}
+}
+
+impl<'s, 'a, T> ParseIterator<'s> for NormalizeCrlf<'a, T>
+where T: ParseIterator<'s> {
+
+	type PointInTime = T::PointInTime;
+
+	fn slice(&self) -> &'s [u8] {
+		self.inner.slice()
+	}
+
+	fn pit(&self) -> Self::PointInTime {
+		self.inner.pit()
+	}
+
+	fn restore_pit(&mut self, pit: Self::PointInTime) {
+		self.inner.restore_pit(pit)
+	}
+
+	fn advance(&mut self) -> Option<()> {
+		self.inner.advance()?;
+
+		// skip the \r if a \n follows
+		if self.inner.byte() == Some(b'\r') && self.inner.peek() == Some(b'\n') {
+			self.inner.advance()
+		} else {
+			Some(())
+		}
+	}
+
+	fn recorder(&self) -> Option<&Recorder> {
+		self.inner.recorder()
+	}
+
+	fn recorder_depth(&self) -> usize {
+		self.inner.recorder_depth()
+	}
+
+	fn rewind(&mut self, len: usize) -> Result<&mut Self, usize> {
+		self.inner.rewind(len)?;
+		Ok(self)
+	}
+
+	#[inline]
+	unsafe fn is_valid_utf8() -> bool {
+		T::is_valid_utf8()
+	}
+
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+	use crate::*;
+
+	#[test]
+	fn test_normalize_crlf() {
+
+		let mut parser = Parser::new(b"a\r\nb\rc\n");
+		let mut parser = parser.normalize_crlf();
+
+		assert_eq!(b'a', parser.next().unwrap());
+		assert_eq!(b'\n', parser.next().unwrap());
+		assert_eq!(b'b', parser.next().unwrap());
+		// a lonely \r is kept
+		assert_eq!(b'\r', parser.next().unwrap());
+		assert_eq!(b'c', parser.next().unwrap());
+		assert_eq!(b'\n', parser.next().unwrap());
+		assert!(parser.next().is_none());
+
+	}
+
+	#[test]
+	fn record_keeps_cr() {
+
+		let mut parser = StrParser::new("a\r\nb");
+		let mut parser = parser.normalize_crlf();
+		let s = parser
+			.record()
+			.consume_len(2).unwrap()
+			.to_str();
+		// the \r is still part of the underlying slice
+		assert_eq!(s, "a\r\n");
+
+	}
+
+	#[test]
+	fn split_lines() {
+
+		let mut parser = StrParser::new("a\r\nb\nc");
+		let lines: Vec<_> = parser
+			.normalize_crlf()
+			.split_on_byte(b'\n')
+			.map_and_collect(|line| line.record().consume_to_str());
+
+		assert_eq!(lines, ["a", "b", "c"]);
+
+		let mut parser = StrParser::new("a\r\nb\nc");
+		let mut c = 0;
+		parser
+			.normalize_crlf()
+			.split_on_byte(b'\n')
+			.for_each(|line| {
+				c += 1;
+				while let Some(b) = line.next() {
+					assert_ne!(b, b'\r');
+				}
+			});
+		assert_eq!(c, 3);
+
+	}
+
+}
@@ -0,0 +1,348 @@
+//!
+//! Parse integers, floating point numbers and hex values.
+//!
+//! ## Example
+//! ```
+//! # use byte_parser::{StrParser, ParseIterator, numbers::{parse_int, parse_int_radix}};
+//! let mut parser = StrParser::new("-42,ff");
+//! let mut split = parser.split_on_byte(b',');
+//!
+//! let num: i32 = parse_int(split.next().unwrap()).unwrap();
+//! assert_eq!(num, -42);
+//!
+//! let num: u8 = parse_int_radix(split.next().unwrap(), 16).unwrap();
+//! assert_eq!(num, 255);
+//! ```
+
+use crate::ParseIterator;
+
+use core::str::FromStr;
+use core::num::ParseIntError;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+
+/// Integers which can be parsed with a radix.
+pub trait FromStrRadix: Sized {
+	fn from_str_radix(s: &str, radix: u32) -> Result<Self, ParseIntError>;
+}
+
+macro_rules! from_str_radix {
+	($($ty:ty),*) => {$(
+		impl FromStrRadix for $ty {
+			#[inline]
+			fn from_str_radix(s: &str, radix: u32) -> Result<Self, ParseIntError> {
+				<$ty>::from_str_radix(s, radix)
+			}
+		}
+	)*}
+}
+
+from_str_radix!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+
+/// Parses an optional `+` or `-` sign followed by at least one ascii digit
+/// into `T`. Bytes after the digits are not consumed.
+///
+/// Returns `None` without advancing if no digit was found or if `T`
+/// could not be parsed, for example because the number is too large.
+pub fn parse_int<'s, I, T>(iter: &mut I) -> Option<T>
+where
+	I: ParseIterator<'s>,
+	T: FromStr {
+	parse_with(iter, 10, |s| s.parse().ok())
+}
+
+/// Like `parse_int` but with digits in the given `radix`. Letters are
+/// accepted in both cases, no prefix like `0x` is consumed.
+///
+/// ## Panics
+/// Panics if `radix` is not in the range from 2 to 36.
+pub fn parse_int_radix<'s, I, T>(iter: &mut I, radix: u32) -> Option<T>
+where
+	I: ParseIterator<'s>,
+	T: FromStrRadix {
+	assert!(
+		(2..=36).contains(&radix),
+		"radix must be in the range from 2 to 36"
+	);
+	parse_with(iter, radix, |s| T::from_str_radix(s, radix).ok())
+}
+
+/// Parses a floating point number into `T`. An optional `+` or `-` sign
+/// is followed by an integer part and a fraction, where one of them can be
+/// empty, and an optional exponent like `e-3`. Bytes after the number are
+/// not consumed.
+///
+/// Returns `None` without advancing if no number was found or if `T`
+/// could not be parsed.
+///
+/// ## Example
+/// ```
+/// # use byte_parser::{StrParser, ParseIterator, numbers::parse_float};
+/// let mut parser = StrParser::new("-1.5e3 .5 1e");
+/// assert_eq!(parse_float(&mut parser), Some(-1500f64));
+/// parser.advance();
+/// assert_eq!(parse_float(&mut parser), Some(0.5f32));
+/// parser.advance();
+/// // an exponent without digits is not part of the number
+/// assert_eq!(parse_float(&mut parser), Some(1f32));
+/// assert_eq!(parser.next(), Some(b'e'));
+/// ```
+pub fn parse_float<'s, I, T>(iter: &mut I) -> Option<T>
+where
+	I: ParseIterator<'s>,
+	T: FromStr {
+	iter.attempt(|iter| {
+		let mut iter = iter.record();
+		iter.next_if(is_sign);
+
+		let mut digits = iter.count_while_byte_fn(u8::is_ascii_digit);
+		if iter.next_if(|&b| b == b'.').is_some() {
+			digits += iter.count_while_byte_fn(u8::is_ascii_digit);
+		}
+		if digits == 0 {
+			return None
+		}
+
+		iter.attempt(|iter| {
+			iter.next_if(|&b| b == b'e' || b == b'E')?;
+			iter.next_if(is_sign);
+			(iter.count_while_byte_fn(u8::is_ascii_digit) > 0).then(|| ())
+		});
+
+		str_of(&iter)?.parse().ok()
+	})
+}
+
+/// Parses exactly two hex digits.
+///
+/// Returns `None` without advancing if the next two bytes are not hex
+/// digits.
+///
+/// ## Example
+/// ```
+/// # use byte_parser::{StrParser, ParseIterator, numbers::parse_hex_u8};
+/// let mut parser = StrParser::new(r"\x1bA");
+/// parser.expect_bytes(b"\\x").unwrap();
+/// assert_eq!(parse_hex_u8(&mut parser), Some(0x1b));
+/// assert!(parse_hex_u8(&mut parser).is_none());
+/// assert_eq!(parser.next(), Some(b'A'));
+/// ```
+pub fn parse_hex_u8<'s, I>(iter: &mut I) -> Option<u8>
+where I: ParseIterator<'s> {
+	parse_hex::<_, 2>(iter).map(|n| n as u8)
+}
+
+/// Parses exactly eight hex digits.
+///
+/// Returns `None` without advancing if the next eight bytes are not hex
+/// digits.
+pub fn parse_hex_u32<'s, I>(iter: &mut I) -> Option<u32>
+where I: ParseIterator<'s> {
+	parse_hex::<_, 8>(iter)
+}
+
+/// Parses pairs of hex digits, pushing the decoded bytes to `out` and
+/// returning how many bytes were pushed.
+///
+/// Stops at the first pair which is not made of two hex digits, so a
+/// trailing odd digit is not consumed.
+///
+/// ## Example
+/// ```
+/// # use byte_parser::{StrParser, ParseIterator, numbers::parse_hex_bytes};
+/// let mut parser = StrParser::new("deadBEEF1");
+/// let mut out = vec![];
+/// assert_eq!(parse_hex_bytes(&mut parser, &mut out), 4);
+/// assert_eq!(out, [0xde, 0xad, 0xbe, 0xef]);
+/// assert_eq!(parser.next(), Some(b'1'));
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub fn parse_hex_bytes<'s, I>(iter: &mut I, out: &mut Vec<u8>) -> usize
+where I: ParseIterator<'s> {
+	let mut count = 0;
+	while let Some(b) = parse_hex_u8(iter) {
+		out.push(b);
+		count += 1;
+	}
+	count
+}
+
+pub(crate) fn parse_hex<'s, I, const N: usize>(iter: &mut I) -> Option<u32>
+where I: ParseIterator<'s> {
+	iter.attempt(|iter| {
+		iter.next_array::<N>()?
+			.iter()
+			.try_fold(0, |n, &b| Some(n << 4 | (b as char).to_digit(16)?))
+	})
+}
+
+fn parse_with<'s, I, T, F>(iter: &mut I, radix: u32, f: F) -> Option<T>
+where
+	I: ParseIterator<'s>,
+	F: FnOnce(&str) -> Option<T> {
+	iter.attempt(|iter| {
+		let mut iter = iter.record();
+		iter.next_if(is_sign);
+
+		iter
+			.while_byte_fn(|&b| (b as char).is_digit(radix))
+			.consume_at_least(1)
+			.ok()?;
+
+		f(str_of(&iter)?)
+	})
+}
+
+fn is_sign(b: &u8) -> bool {
+	matches!(b, b'+' | b'-')
+}
+
+// the recorded bytes are only ascii
+fn str_of<'s, I>(iter: &I) -> Option<&'s str>
+where I: ParseIterator<'s> {
+	core::str::from_utf8(iter.to_slice()).ok()
+}
+
+
+#[cfg(test)]
+mod tests {
+
+	use super::*;
+	use crate::*;
+
+	#[test]
+	fn decimal() {
+
+		let mut parser = StrParser::new("+12a");
+		assert_eq!(parse_int::<_, u32>(&mut parser), Some(12));
+		assert_eq!(parser.next(), Some(b'a'));
+
+		let mut parser = Parser::new(b"-128");
+		assert_eq!(parse_int::<_, i8>(&mut parser), Some(-128));
+		assert!(parser.next().is_none());
+
+		let mut parser = StrParser::new("007");
+		assert_eq!(parse_int::<_, u64>(&mut parser), Some(7));
+
+	}
+
+	#[test]
+	fn radix() {
+
+		let mut parser = StrParser::new("-7fG");
+		assert_eq!(parse_int_radix::<_, i32>(&mut parser, 16), Some(-0x7f));
+		assert_eq!(parser.next(), Some(b'G'));
+
+		let mut parser = StrParser::new("1012");
+		assert_eq!(parse_int_radix::<_, u8>(&mut parser, 2), Some(0b101));
+		assert_eq!(parser.next(), Some(b'2'));
+
+		let mut parser = StrParser::new("17");
+		assert_eq!(parse_int_radix::<_, u8>(&mut parser, 8), Some(0o17));
+
+	}
+
+	#[test]
+	fn float() {
+
+		let cases: [(&str, f64, usize); 9] = [
+			("1", 1.0, 1),
+			("-2.5", -2.5, 4),
+			("+.5", 0.5, 3),
+			("3.", 3.0, 2),
+			("1e3", 1000.0, 3),
+			("1.5E-2", 0.015, 6),
+			("2e+2x", 200.0, 4),
+			(".5e", 0.5, 2),
+			("4e-", 4.0, 1)
+		];
+
+		for (case, num, len) in cases.iter() {
+			let mut parser = StrParser::new(case);
+			assert_eq!(parse_float::<_, f64>(&mut parser), Some(*num), "{}", case);
+			assert_eq!(parser.offset(), *len, "{}", case);
+		}
+
+		let mut parser = StrParser::new("0.1");
+		assert_eq!(parse_float::<_, f32>(&mut parser), Some(0.1f32));
+
+	}
+
+	#[test]
+	fn invalid_float() {
+
+		let cases = ["", ".", "-.", "+e1", ".e1", "e1", "a"];
+
+		for case in cases.iter() {
+			let mut parser = StrParser::new(case);
+			assert!(parse_float::<_, f64>(&mut parser).is_none(), "{}", case);
+			assert_eq!(parser.offset(), 0);
+		}
+
+	}
+
+	#[test]
+	fn invalid() {
+
+		let cases = ["", "-", "+a", "a1", "256", "-1"];
+
+		for case in cases.iter() {
+			let mut parser = StrParser::new(case);
+			assert!(parse_int::<_, u8>(&mut parser).is_none(), "{}", case);
+			// nothing was consumed
+			assert_eq!(parser.offset(), 0);
+		}
+
+		let mut parser = StrParser::new("2");
+		assert!(parse_int_radix::<_, u8>(&mut parser, 2).is_none());
+		assert_eq!(parser.offset(), 0);
+
+	}
+
+	#[test]
+	fn hex() {
+
+		let mut parser = StrParser::new("ff0A1b2C3dg");
+		assert_eq!(parse_hex_u8(&mut parser), Some(0xff));
+		assert_eq!(parse_hex_u32(&mut parser), Some(0x0a1b2c3d));
+		assert!(parse_hex_u8(&mut parser).is_none());
+		assert!(parse_hex_u32(&mut parser).is_none());
+		assert_eq!(parser.next(), Some(b'g'));
+
+		// not enough digits
+		let mut parser = StrParser::new("1234567");
+		assert!(parse_hex_u32(&mut parser).is_none());
+		assert_eq!(parser.offset(), 0);
+
+		let mut parser = StrParser::new("f");
+		assert!(parse_hex_u8(&mut parser).is_none());
+		assert_eq!(parser.offset(), 0);
+
+	}
+
+	#[cfg(feature = "alloc")]
+	#[test]
+	fn hex_bytes() {
+
+		let mut out = vec![1];
+		let mut parser = StrParser::new("00ffxx");
+		assert_eq!(parse_hex_bytes(&mut parser, &mut out), 2);
+		assert_eq!(out, [1, 0, 0xff]);
+		assert_eq!(parse_hex_bytes(&mut parser, &mut out), 0);
+		assert_eq!(parser.next(), Some(b'x'));
+
+		let mut parser = StrParser::new("");
+		assert_eq!(parse_hex_bytes(&mut parser, &mut out), 0);
+
+	}
+
+	#[test]
+	#[should_panic]
+	fn invalid_radix() {
+		parse_int_radix::<_, u8>(&mut StrParser::new("1"), 37);
+	}
+
+}
@@ -1,13 +1,51 @@
 
 use crate::{
 	pit::PointInTime,
-	ignore_byte::IgnoreByte,
+	ignore_byte::{IgnoreByte, IgnoreBytes, IgnoreByteFn},
+	normalize_crlf::NormalizeCrlf,
 	while_byte_fn::WhileByteFn,
-	split_on_byte::SplitOnByte,
+	while_char_fn::WhileCharFn,
+	split_on_byte::{SplitOnByte, SplitOnByteIter},
+	lines::Lines,
+	split_on_slice::SplitOnSlice,
+	split_on_any_byte::SplitOnAnyByte,
+	split_on_fn::SplitOnFn,
+	split_respecting::SplitRespecting,
+	rsplit_on_byte::RSplitOnByte,
 	recorder::{Recorder, RecordIter},
 	stop::Stop,
-	expect_byte::ExpectByte
+	take::Take,
+	bytes::Bytes,
+	positions::Positions,
+	key_value::KeyValues,
+	consumption_guard::FullConsumptionGuard,
+	checkpoint::Checkpoint,
+	byte_set::ByteSet,
+	expect_byte::ExpectByte,
+	error::ParseError,
+	byte_search::{find_byte, find_not_byte}
 };
+#[cfg(feature = "unstable-parse-iter")]
+use crate::parse_iter::ParseIter;
+#[cfg(feature = "alloc")]
+use crate::ignore_byte::IgnoreByteCompacting;
+#[cfg(feature = "alloc")]
+use alloc::{vec::Vec, string::String, borrow::Cow};
+
+// generates next_* methods which read an integer from a fixed number of bytes
+macro_rules! next_int {
+	($($name:ident, $ty:ty, $from:ident, $order:literal;)*) => {$(
+		#[doc = concat!(
+			"Reads a `", stringify!($ty), "` in ", $order,
+			" byte order.\nReturns `None` without advancing if not enough ",
+			"bytes remain."
+		)]
+		#[inline]
+		fn $name(&mut self) -> Option<$ty> {
+			self.next_array().map(<$ty>::$from)
+		}
+	)*}
+}
 
 /// The main trait of this crate.
 ///
@@ -36,9 +74,192 @@ pub trait ParseIterator<'s> {// s for slice
 	/// Advances the internal position.
 	fn advance(&mut self) -> Option<()>;
 
+	/// Advances up to `n` times and returns how many times it advanced.
+	/// Returns `None` if `n` is not zero and the iterator was already empty.
+	///
+	/// The base parsers do this in one step, adapters call `advance` `n`
+	/// times since one `advance` can move more than one byte.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("abc");
+	/// assert_eq!(parser.advance_n(2), Some(2));
+	/// assert_eq!(parser.advance_n(2), Some(1));
+	/// assert_eq!(parser.advance_n(2), None);
+	/// assert_eq!(parser.advance_n(0), Some(0));
+	/// ```
+	#[inline]
+	fn advance_n(&mut self, n: usize) -> Option<usize> {
+		for i in 0..n {
+			if self.advance().is_none() {
+				return match i {
+					0 => None,
+					i => Some(i)
+				}
+			}
+		}
+
+		Some(n)
+	}
+
 	/// Returns a `Recorder` if recording was started.
 	fn recorder(&self) -> Option<&Recorder>;
 
+	/// Returns the offset of the next byte in the underlying slice,
+	/// `0` if nothing was consumed yet.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{Parser, ParseIterator};
+	/// let mut parser = Parser::new(b"abc");
+	/// assert_eq!(parser.offset(), 0);
+	/// parser.next();
+	/// assert_eq!(parser.offset(), 1);
+	/// parser.consume();
+	/// assert_eq!(parser.offset(), 3);
+	/// ```
+	#[inline]
+	fn offset(&self) -> usize {
+		// Position + 1 returns 0 if nothing was consumed
+		self.pit().pos() + 1
+	}
+
+	/// Returns the part of the underlying slice which was not consumed yet,
+	/// without advancing.
+	///
+	/// This ignores any bounds a wrapper might have, for example the end of
+	/// a segment in `split_on_byte`.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{Parser, ParseIterator};
+	/// let mut parser = Parser::new(b"abc");
+	/// assert_eq!(parser.remaining(), b"abc");
+	/// parser.next();
+	/// assert_eq!(parser.remaining(), b"bc");
+	/// parser.consume();
+	/// assert_eq!(parser.remaining(), b"");
+	/// ```
+	#[inline]
+	fn remaining(&self) -> &'s [u8] {
+		&self.slice()[self.offset()..]
+	}
+
+	/// Returns the part of the underlying slice which was not consumed yet
+	/// as a `str`, without advancing.
+	///
+	/// ## Panics
+	/// Panics if invalid utf8 is present.
+	#[inline]
+	fn remaining_str(&self) -> &'s str {
+		let remaining = self.remaining();
+		if unsafe { Self::is_valid_utf8() } {
+			// Safe because is_valid_utf8 guaranties everything is valid utf8
+			unsafe { core::str::from_utf8_unchecked(remaining) }
+		} else {
+			core::str::from_utf8(remaining).expect("invalid utf8")
+		}
+	}
+
+	/// Returns the part of the underlying slice which was not consumed yet
+	/// as a `str`, without advancing.
+	///
+	/// Returns an error if invalid utf8 is present, which can't happen if
+	/// `is_valid_utf8` returns `true` like for `StrParser`.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{Parser, ParseIterator};
+	/// let mut parser = Parser::new(b"key: value");
+	/// let rest = parser.try_remaining_str().unwrap();
+	/// let colon = rest.find(": ").unwrap();
+	/// parser.consume_len(colon + 2).unwrap();
+	/// assert_eq!(parser.try_remaining_str(), Ok("value"));
+	///
+	/// assert!(Parser::new(b"\xff").try_remaining_str().is_err());
+	/// ```
+	#[inline]
+	fn try_remaining_str(&self) -> Result<&'s str, core::str::Utf8Error> {
+		let remaining = self.remaining();
+		if unsafe { Self::is_valid_utf8() } {
+			// Safe because is_valid_utf8 guaranties everything is valid utf8
+			Ok(unsafe { core::str::from_utf8_unchecked(remaining) })
+		} else {
+			core::str::from_utf8(remaining)
+		}
+	}
+
+	/// Returns the number of bytes which were not consumed yet, without
+	/// advancing.
+	///
+	/// This respects the bounds of wrappers, for a split segment only the
+	/// bytes of the segment are counted. Since the bytes need to be walked
+	/// this takes `&mut self` like `is_empty`.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{Parser, ParseIterator};
+	/// let mut parser = Parser::new(b"ab,c");
+	/// assert_eq!(parser.len(), 4);
+	///
+	/// let mut split = parser.split_on_byte(b',');
+	/// let ab = split.next().unwrap();
+	/// assert_eq!(ab.len(), 2);
+	/// ab.next();
+	/// assert_eq!(ab.len(), 1);
+	/// ```
+	#[inline]
+	fn len(&mut self) -> usize
+	where Self: Sized {
+		let pit = self.pit();
+		let len = self.record().consume_to_slice().len();
+		self.restore_pit(pit);
+		len
+	}
+
+	/// Returns `true` if there are no more bytes to consume.
+	///
+	/// This respects the bounds of wrappers and is the same as
+	/// `peek().is_none()`.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{Parser, ParseIterator};
+	/// let mut parser = Parser::new(b"a,b");
+	/// assert!(!parser.is_empty());
+	///
+	/// let mut split = parser.split_on_byte(b',');
+	/// let a = split.next().unwrap();
+	/// a.consume();
+	/// assert!(a.is_empty());
+	/// ```
+	#[inline]
+	fn is_empty(&mut self) -> bool {
+		self.peek().is_none()
+	}
+
+	/// Returns how many recorders are active, meaning how many `RecordIter`
+	/// wrap this iterator.
+	///
+	/// `recorder` always returns the innermost recorder, so `to_slice` starts
+	/// from where the last `record` was called.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{Parser, ParseIterator};
+	/// let mut parser = Parser::new(b"abc");
+	/// assert_eq!(parser.recorder_depth(), 0);
+	/// let mut outer = parser.record();
+	/// assert_eq!(outer.recorder_depth(), 1);
+	/// let inner = outer.record();
+	/// assert_eq!(inner.recorder_depth(), 2);
+	/// ```
+	#[inline]
+	fn recorder_depth(&self) -> usize {
+		self.recorder().map_or(0, |_| 1)
+	}
+
 	/// Advances if `advance_if` returns `true`. 
 	/// Returns `None` if the iterator is empty.
 	fn advance_if<F>(&mut self, advance_if: F) -> Option<bool>
@@ -49,6 +270,24 @@ pub trait ParseIterator<'s> {// s for slice
 		}
 	}
 
+	/// Advances and returns the next byte if it is contained in `set`.
+	/// Else returns `None` without advancing.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("ab, cd");
+	/// parser.consume_until_any_byte(b", ");
+	/// assert_eq!(parser.advance_if_any_byte(b", "), Some(b','));
+	/// assert_eq!(parser.advance_if_any_byte(b", "), Some(b' '));
+	/// assert!(parser.advance_if_any_byte(b", ").is_none());
+	/// assert_eq!(parser.next(), Some(b'c'));
+	/// ```
+	#[inline]
+	fn advance_if_any_byte(&mut self, set: &[u8]) -> Option<u8> {
+		self.expect_one_of(set).ok()
+	}
+
 	/// Returns the current byte if it exists.
 	#[inline]
 	fn byte(&self) -> Option<u8> {
@@ -85,434 +324,3374 @@ pub trait ParseIterator<'s> {// s for slice
 		n
 	}
 
-	/// Returns the next x bytes without advancing the internal position.
+	/// Returns the next `N` bytes and advances the internal position.
+	/// Returns `None` without advancing if less than `N` bytes remain.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{Parser, ParseIterator};
+	/// let mut parser = Parser::new(&[0, 0, 1, 0, 7]);
+	/// let len = u32::from_be_bytes(parser.next_array().unwrap());
+	/// assert_eq!(len, 256);
+	/// assert!(parser.next_array::<2>().is_none());
+	/// assert_eq!(parser.next(), Some(7));
+	/// ```
 	#[inline]
-	fn peek_len(&mut self, len: usize) -> Option<&'s [u8]>
-	where Self: Sized {
+	fn next_array<const N: usize>(&mut self) -> Option<[u8; N]> {
 		let pit = self.pit();
-		let s = self.record()
-			.consume_len(len)
-			.map(|iter| iter.to_slice())
-			.ok();
-		self.restore_pit(pit);
-		s
+		let mut arr = [0; N];
+
+		for b in arr.iter_mut() {
+			match self.next() {
+				Some(n) => *b = n,
+				None => {
+					self.restore_pit(pit);
+					return None
+				}
+			}
+		}
+
+		Some(arr)
 	}
 
-	/// Tries to get the byte at the given position, without advancing.
+	/// Returns the next `N` bytes without advancing the internal position.
 	#[inline]
-	fn peek_at(&mut self, pos: usize) -> Option<u8> {
-		assert!(pos > 0, "peek_at pos must be bigger than 0");
-
+	fn peek_array<const N: usize>(&mut self) -> Option<[u8; N]> {
 		let pit = self.pit();
-		let n = self.consume_len(pos - 1).ok()
-			.map(|p| p.next())
-			.flatten();
+		let arr = self.next_array();
 		self.restore_pit(pit);
-		n
+		arr
 	}
 
-	/// Skips a given byte when calling next.
-	///
-	/// ## Warning
-	/// If you later call `to_slice` or a similar methods
-	/// the skipped byte will still be returned.
+	/// Returns the next two bytes without advancing the internal position.
 	///
 	/// ## Example
 	/// ```
-	/// # use byte_parser::{Parser, ParseIterator};
-	/// let mut parser = Parser::new(b"abc");
-	/// let mut parser = parser.ignore_byte(b'b');
-	/// assert_eq!(b'a', parser.next().unwrap());
-	/// assert_eq!(b'c', parser.next().unwrap());
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("<=");
+	/// let len = match parser.peek2() {
+	/// 	Some((b'<', b'=')) | Some((b'>', b'=')) => 2,
+	/// 	_ => 1
+	/// };
+	/// assert_eq!(len, 2);
 	/// ```
 	#[inline]
-	fn ignore_byte(&mut self, byte: u8) -> IgnoreByte<'_, Self>
-	where Self: Sized {
-		IgnoreByte::new(self, byte)
+	fn peek2(&mut self) -> Option<(u8, u8)> {
+		let [a, b] = self.peek_array()?;
+		Some((a, b))
 	}
 
-	/// Advances while the function returns `true`.
+	/// Returns the next three bytes without advancing the internal position.
 	#[inline]
-	fn while_byte_fn<F>(&mut self, f: F) -> WhileByteFn<'_, Self, F>
-	where 
-		Self: Sized,
-		F: Fn(&u8) -> bool {
-		WhileByteFn::new(self, f)
+	fn peek3(&mut self) -> Option<(u8, u8, u8)> {
+		let [a, b, c] = self.peek_array()?;
+		Some((a, b, c))
 	}
 
-	/// Consumes until the iterator is empty. 
-	/// Meaning that `advance` returns None.
-	#[inline]
-	fn consume(&mut self) -> &mut Self {
-		while let Some(_) = self.advance() {}
-		self
+	next_int! {
+		next_u16_le, u16, from_le_bytes, "little endian";
+		next_u16_be, u16, from_be_bytes, "big endian";
+		next_u32_le, u32, from_le_bytes, "little endian";
+		next_u32_be, u32, from_be_bytes, "big endian";
+		next_u64_le, u64, from_le_bytes, "little endian";
+		next_u64_be, u64, from_be_bytes, "big endian";
+		next_i16_le, i16, from_le_bytes, "little endian";
+		next_i16_be, i16, from_be_bytes, "big endian";
+		next_i32_le, i32, from_le_bytes, "little endian";
+		next_i32_be, i32, from_be_bytes, "big endian";
+		next_i64_le, i64, from_le_bytes, "little endian";
+		next_i64_be, i64, from_be_bytes, "big endian";
 	}
 
-	/// Consumes until the iterator is empty,
-	/// and returns how many times advance got called.
+	/// Returns the next char and advances the internal position
+	/// by the length of the char.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("aü😀");
+	/// assert_eq!(parser.next_char(), Some('a'));
+	/// assert_eq!(parser.peek_char(), Some('ü'));
+	/// assert_eq!(parser.next_char(), Some('ü'));
+	/// assert_eq!(parser.next_char(), Some('😀'));
+	/// assert!(parser.next_char().is_none());
+	/// ```
+	///
+	/// Returns `None` without advancing if the next bytes are not valid
+	/// utf8, for example with a `Parser` or in the middle of a char. Use
+	/// `try_next_char` to differentiate this from the end.
 	#[inline]
-	fn consume_and_count(&mut self) -> usize {
-		let mut c = 0;
-		while let Some(_) = self.advance() {
-			c += 1
-		}
-		c
+	fn next_char(&mut self) -> Option<char>
+	where Self: Sized {
+		self.try_next_char().ok().flatten()
 	}
 
-	/// Consumes a given length. Returns how much was consumed if could
-	/// not consume all.
+	/// Returns the next char without advancing the internal position.
+	///
+	/// Returns `None` if the next bytes are not valid utf8, see
+	/// `next_char`.
 	#[inline]
-	fn consume_len(&mut self, len: usize) -> Result<&mut Self, usize> {
-		// we can not just increase the internal position
-		// because calling advance could increase the position more than once
-		for i in 0..len {
-			self.advance().ok_or(i)?;
-		}
-
-		Ok(self)
+	fn peek_char(&mut self) -> Option<char>
+	where Self: Sized {
+		let pit = self.pit();
+		let c = self.next_char();
+		self.restore_pit(pit);
+		c
 	}
 
-	/// Consumes until the iterator is empty. 
-	/// Returns `Err(len)` if could not consume `len`.
-	#[inline]
-	fn consume_at_least(&mut self, len: usize) -> Result<&mut Self, usize> {
-		self.consume_len(len)?;
-		Ok(self.consume())
+	/// Returns the next char and advances the internal position
+	/// by the length of the char.
+	///
+	/// If the next bytes are not valid utf8 (or a char is cut off by the end)
+	/// an error is returned and the position is not changed.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{Parser, ParseIterator};
+	/// let mut parser = Parser::new(b"a\xc3");
+	/// assert_eq!(parser.try_next_char().unwrap(), Some('a'));
+	/// assert!(parser.try_next_char().is_err());
+	/// assert_eq!(parser.next(), Some(0xc3));
+	/// assert_eq!(parser.try_next_char().unwrap(), None);
+	/// ```
+	fn try_next_char(&mut self) -> Result<Option<char>, core::str::Utf8Error>
+	where Self: Sized {
+		let width = match self.peek() {
+			Some(b) => utf8_char_width(b),
+			None => return Ok(None)
+		};
+
+		let pit = self.pit();
+		let mut rec = self.record();
+		// if the char is cut off the slice will be invalid utf8
+		let _ = rec.consume_len(width);
+
+		match core::str::from_utf8(rec.to_slice()) {
+			Ok(s) => Ok(s.chars().next()),
+			Err(e) => {
+				self.restore_pit(pit);
+				Err(e)
+			}
+		}
 	}
 
-	/// Consumes until the iterator is empty, returning how much was consumed. 
-	/// Returns `Err(len)` if could not consume `len`.
+	/// Returns the next x bytes without advancing the internal position.
 	#[inline]
-	fn consume_at_least_and_count(&mut self, len: usize) -> Result<usize, usize> {
-		self.consume_len(len)?;
-		Ok(self.consume_and_count() + len)
+	fn peek_len(&mut self, len: usize) -> Option<&'s [u8]>
+	where Self: Sized {
+		let pit = self.pit();
+		let s = self.record()
+			.consume_len(len)
+			.map(|iter| iter.to_slice())
+			.ok();
+		self.restore_pit(pit);
+		s
 	}
 
-	/// Consumes while the function returns `true`.
+	/// Returns the bytes for which `f` returns `true`,
+	/// without advancing the internal position.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("42px");
+	/// if !parser.peek_while_byte_fn(u8::is_ascii_digit).is_empty() {
+	/// 	let num = parser.take_while_byte_fn_str(u8::is_ascii_digit);
+	/// 	assert_eq!(num, "42");
+	/// }
+	/// ```
 	#[inline]
-	fn consume_while_byte_fn<F>(&mut self, f: F) -> &mut Self
-	where 
+	fn peek_while_byte_fn<F>(&mut self, f: F) -> &'s [u8]
+	where
 		Self: Sized,
 		F: Fn(&u8) -> bool {
-		self.while_byte_fn(f).consume();
-		self
+		let pit = self.pit();
+		let s = self.take_while_byte_fn(f);
+		self.restore_pit(pit);
+		s
 	}
 
-	/// Consumes while a give `byte` is returned.
+	/// Returns `true` if the next bytes are equal to `prefix`,
+	/// without advancing the internal position.
+	///
+	/// Returns `false` if less bytes than `prefix.len()` remain.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("<!-- comment -->");
+	/// assert!(parser.starts_with(b"<!--"));
+	/// assert!(!parser.starts_with(b"<!DOCTYPE"));
+	/// assert_eq!(parser.next(), Some(b'<'));
+	/// ```
 	#[inline]
-	fn consume_while_byte(&mut self, byte: u8) -> &mut Self
-	where Self: Sized {
-		self.consume_while_byte_fn(|&b| b == byte)
+	fn starts_with(&mut self, prefix: &[u8]) -> bool {
+		let pit = self.pit();
+		let eq = expect_slice_with(self, prefix, |a, b| a == b).is_ok();
+		self.restore_pit(pit);
+		eq
 	}
 
-	// Consumes while an ascii whitespace is returned.
-	// #[inline]
-	// fn consume_while_ascii_whitespace(&mut self) -> &mut Self
-	// where Self: Sized {
-	// 	self.consume_while_byte_fn(u8::is_ascii_whitespace)
-	// }
+	/// Returns `true` if the next bytes are equal to `prefix` ignoring the
+	/// ascii case, without advancing the internal position.
+	///
+	/// Returns `false` if less bytes than `prefix.len()` remain.
+	#[inline]
+	fn starts_with_ignore_ascii_case(&mut self, prefix: &[u8]) -> bool {
+		let pit = self.pit();
+		let eq = expect_slice_with(self, prefix, |a, b| a.eq_ignore_ascii_case(&b))
+			.is_ok();
+		self.restore_pit(pit);
+		eq
+	}
 
-	/// Splits the iterator at a given byte.
+	/// Returns `true` if the next bytes are equal to `s` ignoring the
+	/// ascii case, without advancing the internal position.
+	///
+	/// Returns `false` if less bytes than `s.len()` remain.
+	/// This is the same as `starts_with_ignore_ascii_case`.
 	///
 	/// ## Example
 	/// ```
 	/// # use byte_parser::{StrParser, ParseIterator};
-	/// let mut parser = StrParser::new("Hello World!");
-	/// let mut splitter = parser.split_on_byte(b' ');
-	///
-	/// let hello = splitter.next().unwrap()
-	/// 	.record().consume_to_str();
-	/// let world = splitter.next().unwrap()
-	/// 	.record().consume_to_str();
-	///
-	/// assert_eq!(hello, "Hello");
-	/// assert_eq!(world, "World!");
-	/// assert!(splitter.next().is_none());
+	/// let mut parser = StrParser::new("Select *");
+	/// assert!(parser.peek_slice_eq_ci(b"SELECT"));
+	/// assert!(!parser.peek_slice_eq_ci(b"SELECTED"));
+	/// assert_eq!(parser.next(), Some(b'S'));
 	/// ```
 	#[inline]
-	fn split_on_byte(&mut self, byte: u8) -> SplitOnByte<'_, Self>
-	where Self: Sized {
-		SplitOnByte::new(self, byte)
+	fn peek_slice_eq_ci(&mut self, s: &[u8]) -> bool {
+		self.starts_with_ignore_ascii_case(s)
 	}
 
+	/// Tries to get the byte at the given position, without advancing.
+	///
+	/// `peek_at(1)` returns the next byte.
+	#[deprecated(since = "0.2.4", note = "use the zero based peek_nth")]
 	#[inline]
-	fn count_byte(&mut self, byte: u8) -> usize
-	where Self: Sized {
-		self.while_byte_fn(|&b| b == byte)
-			.consume_and_count()
+	fn peek_at(&mut self, pos: usize) -> Option<u8> {
+		assert!(pos > 0, "peek_at pos must be bigger than 0");
+		self.peek_nth(pos - 1)
 	}
 
-	/// Starts a new `Recorder` which starts recording at this position.
-	#[inline]
-	fn record(&mut self) -> RecordIter<'_, Self>
+	/// Returns the `n`th next byte without advancing, `peek_nth(0)` is the
+	/// same as `peek`.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("ab");
+	/// assert_eq!(parser.peek_nth(0), Some(b'a'));
+	/// assert_eq!(parser.peek_nth(1), Some(b'b'));
+	/// assert!(parser.peek_nth(2).is_none());
+	/// ```
+	#[inline]
+	fn peek_nth(&mut self, n: usize) -> Option<u8> {
+		let pit = self.pit();
+		let b = match self.advance_n(n) {
+			Some(advanced) if advanced == n => self.next(),
+			_ => None
+		};
+		self.restore_pit(pit);
+		b
+	}
+
+	/// Returns the byte at the current position in the underlying slice,
+	/// which is the byte that was returned last by `next`.
+	///
+	/// Returns `None` if nothing was consumed yet. After a split segment
+	/// ended this is the split byte, which `next` never returned.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("1-2");
+	/// assert!(parser.prev_byte().is_none());
+	/// parser.advance();
+	/// assert_eq!(parser.peek(), Some(b'-'));
+	/// // the minus follows a digit so it is not unary
+	/// assert!(parser.prev_byte().unwrap().is_ascii_digit());
+	/// ```
+	#[inline]
+	fn prev_byte(&self) -> Option<u8> {
+		self.byte()
+	}
+
+	/// Rewinds one byte if `f` returns `true` for the byte which would be
+	/// rewound. Returns `true` if the iterator was rewound.
+	///
+	/// Unlike `prev_byte` this is never the split byte of an ended split
+	/// segment but the last byte of the segment.
+	///
+	/// Returns `false` if nothing was consumed yet or the iterator does
+	/// not allow to rewind.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("abc;def");
+	/// // a greedy match which consumed the delimiter
+	/// parser.consume_len(4).unwrap();
+	/// assert!(parser.rewind_if(|&b| b == b';'));
+	/// assert!(!parser.rewind_if(|&b| b == b';'));
+	/// assert_eq!(parser.peek(), Some(b';'));
+	/// ```
+	#[inline]
+	fn rewind_if<F>(&mut self, f: F) -> bool
+	where F: FnOnce(&u8) -> bool {
+		// the record position excludes bytes like the split byte
+		let b = self.pit().record_pos().opt()
+			.and_then(|pos| self.slice().get(pos).copied());
+		match b {
+			Some(b) if f(&b) => self.rewind(1).is_ok(),
+			_ => false
+		}
+	}
+
+	/// Skips a given byte when calling next.
+	///
+	/// ## Warning
+	/// If you later call `to_slice` or a similar methods
+	/// the skipped byte will still be returned.
+	/// Use `ignore_byte_compacting` to exclude it.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{Parser, ParseIterator};
+	/// let mut parser = Parser::new(b"abc");
+	/// let mut parser = parser.ignore_byte(b'b');
+	/// assert_eq!(b'a', parser.next().unwrap());
+	/// assert_eq!(b'c', parser.next().unwrap());
+	/// ```
+	#[inline]
+	fn ignore_byte(&mut self, byte: u8) -> IgnoreByte<'_, Self>
 	where Self: Sized {
-		RecordIter::new(self)
+		IgnoreByte::new(self, byte)
+	}
+
+	/// Like `ignore_byte` but `consume_compacted` and
+	/// `consume_compacted_str` return the consumed bytes without the
+	/// ignored byte, borrowed if the ignored byte was not present.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("1,234,567");
+	/// let num: u32 = parser
+	/// 	.ignore_byte_compacting(b',')
+	/// 	.consume_compacted_str()
+	/// 	.parse().unwrap();
+	/// assert_eq!(num, 1234567);
+	/// ```
+	#[cfg(feature = "alloc")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+	#[inline]
+	fn ignore_byte_compacting(&mut self, byte: u8) -> IgnoreByteCompacting<'_, Self>
+	where Self: Sized {
+		IgnoreByteCompacting::new(self, byte)
+	}
+
+	/// Skips every byte contained in `bytes` when calling next.
+	///
+	/// ## Warning
+	/// Like with `ignore_byte` the skipped bytes are still returned by
+	/// `to_slice` and similar methods.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("a\r\nb");
+	/// let mut parser = parser.ignore_bytes(b"\r\n");
+	/// assert_eq!(b'a', parser.next().unwrap());
+	/// assert_eq!(b'b', parser.next().unwrap());
+	/// ```
+	#[inline]
+	fn ignore_bytes(&mut self, bytes: &[u8]) -> IgnoreBytes<'_, Self>
+	where Self: Sized {
+		IgnoreBytes::new(self, bytes)
+	}
+
+	/// Skips every byte for which `f` returns `true` when calling next.
+	///
+	/// ## Warning
+	/// Like with `ignore_byte` the skipped bytes are still returned by
+	/// `to_slice` and similar methods.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("a \tb");
+	/// let mut parser = parser.ignore_byte_fn(u8::is_ascii_whitespace);
+	/// assert_eq!(b'a', parser.next().unwrap());
+	/// assert_eq!(b'b', parser.next().unwrap());
+	/// ```
+	#[inline]
+	fn ignore_byte_fn<F>(&mut self, f: F) -> IgnoreByteFn<'_, Self, F>
+	where
+		Self: Sized,
+		F: Fn(&u8) -> bool {
+		IgnoreByteFn::new(self, f)
+	}
+
+	/// Skips a `\r` if it is directly followed by a `\n`, so that
+	/// only unix line endings are observed.
+	///
+	/// ## Warning
+	/// Like with `ignore_byte` the skipped `\r` is still returned by
+	/// `to_slice` and similar methods if it lies inside the recorded region.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("a\r\nb");
+	/// let mut parser = parser.normalize_crlf();
+	/// assert_eq!(b'a', parser.next().unwrap());
+	/// assert_eq!(b'\n', parser.next().unwrap());
+	/// assert_eq!(b'b', parser.next().unwrap());
+	/// ```
+	#[inline]
+	fn normalize_crlf(&mut self) -> NormalizeCrlf<'_, Self>
+	where Self: Sized {
+		NormalizeCrlf::new(self)
+	}
+
+	/// Advances while the function returns `true`.
+	#[inline]
+	fn while_byte_fn<F>(&mut self, f: F) -> WhileByteFn<'_, Self, F>
+	where 
+		Self: Sized,
+		F: Fn(&u8) -> bool {
+		WhileByteFn::new(self, f)
+	}
+
+	/// Advances while the function returns `true` for the next char.
+	///
+	/// A char is always advanced over completely. Stops if invalid utf8 is
+	/// found.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("über alles");
+	/// let word = parser
+	/// 	.record()
+	/// 	.while_char_fn(char::is_alphabetic)
+	/// 	.consume_to_str();
+	/// assert_eq!(word, "über");
+	/// ```
+	#[inline]
+	fn while_char_fn<F>(&mut self, f: F) -> WhileCharFn<'_, Self, F>
+	where
+		Self: Sized,
+		F: Fn(char) -> bool {
+		WhileCharFn::new(self, f)
+	}
+
+	/// Consumes until the iterator is empty. 
+	/// Meaning that `advance` returns None.
+	#[inline]
+	fn consume(&mut self) -> &mut Self {
+		while let Some(_) = self.advance() {}
+		self
+	}
+
+	/// Consumes until the iterator is empty,
+	/// and returns how many times advance got called.
+	#[inline]
+	fn consume_and_count(&mut self) -> usize {
+		let mut c = 0;
+		while let Some(_) = self.advance() {
+			c += 1
+		}
+		c
+	}
+
+	/// Consumes a given length. Returns how much was consumed if could
+	/// not consume all.
+	#[inline]
+	fn consume_len(&mut self, len: usize) -> Result<&mut Self, usize> {
+		match self.advance_n(len) {
+			Some(n) if n == len => Ok(self),
+			Some(n) => Err(n),
+			None => Err(0)
+		}
+	}
+
+	/// Consumes until the iterator is empty, calling `f` with every byte
+	/// returned by `next`.
+	///
+	/// Bytes skipped by `ignore_byte` and similar adapters are not passed
+	/// to `f`, unlike `to_slice` which still contains them.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("a-b-c");
+	/// let mut checksum = 0;
+	/// parser
+	/// 	.ignore_byte(b'-')
+	/// 	.consume_with(|b| checksum ^= b);
+	/// assert_eq!(checksum, b'a' ^ b'b' ^ b'c');
+	/// ```
+	#[inline]
+	fn consume_with<F>(&mut self, mut f: F) -> &mut Self
+	where F: FnMut(u8) {
+		while let Some(b) = self.next() {
+			f(b);
+		}
+		self
+	}
+
+	/// Like `consume_with` but consumes at most `len` bytes. Returns how
+	/// many bytes were consumed if could not consume all.
+	///
+	/// `len` counts the bytes passed to `f`, so ignored bytes are not
+	/// counted.
+	#[inline]
+	fn consume_len_with<F>(&mut self, len: usize, mut f: F) -> Result<&mut Self, usize>
+	where F: FnMut(u8) {
+		for i in 0..len {
+			match self.next() {
+				Some(b) => f(b),
+				None => return Err(i)
+			}
+		}
+		Ok(self)
+	}
+
+	/// Consumes a given length. Returns `None` without advancing
+	/// if could not consume all.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{Parser, ParseIterator};
+	/// let mut parser = Parser::new(b"abc");
+	/// assert!(parser.try_consume_len(4).is_none());
+	/// assert_eq!(parser.try_consume_len(2).unwrap().next(), Some(b'c'));
+	/// ```
+	#[inline]
+	fn try_consume_len(&mut self, len: usize) -> Option<&mut Self> {
+		let pit = self.pit();
+		if self.consume_len(len).is_err() {
+			self.restore_pit(pit);
+			return None
+		}
+
+		Some(self)
+	}
+
+	/// Calls `f` and restores the position if `f` returns `None`.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("false");
+	/// let b = parser
+	/// 	.attempt(|p| p.advance_if_slice(b"true").then(|| true))
+	/// 	.or_else(|| parser.attempt(|p| {
+	/// 		p.advance_if_slice(b"false").then(|| false)
+	/// 	}));
+	/// assert_eq!(b, Some(false));
+	/// ```
+	#[inline]
+	fn attempt<F, O>(&mut self, f: F) -> Option<O>
+	where F: FnOnce(&mut Self) -> Option<O> {
+		let pit = self.pit();
+		let o = f(self);
+		if o.is_none() {
+			self.restore_pit(pit);
+		}
+		o
+	}
+
+	/// Moves the position back by `len` bytes of the underlying slice.
+	/// Returns `Err(max)` with how many bytes could have been rewound
+	/// if `len` would go before the start, the position is not changed.
+	///
+	/// Split iterators and `record` don't allow to go before the start of
+	/// the segment or recording.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("abc");
+	/// parser.consume_len(2).unwrap();
+	/// assert_eq!(parser.rewind(3).unwrap_err(), 2);
+	///
+	/// parser.rewind(1).unwrap();
+	/// assert_eq!(parser.record().consume_to_str(), "bc");
+	/// ```
+	#[inline]
+	fn rewind(&mut self, len: usize) -> Result<&mut Self, usize> {
+		let mut pit = self.pit();
+		let pos = pit.pos().checked_sub(len)
+			.ok_or(self.offset())?;
+
+		// safe because the position is before the current one
+		unsafe { pit.set_pos(pos) };
+		self.restore_pit(pit);
+
+		Ok(self)
+	}
+
+	/// Consumes until the iterator is empty. 
+	/// Returns `Err(len)` if could not consume `len`.
+	#[inline]
+	fn consume_at_least(&mut self, len: usize) -> Result<&mut Self, usize> {
+		self.consume_len(len)?;
+		Ok(self.consume())
+	}
+
+	/// Consumes until the iterator is empty, returning how much was consumed. 
+	/// Returns `Err(len)` if could not consume `len`.
+	#[inline]
+	fn consume_at_least_and_count(&mut self, len: usize) -> Result<usize, usize> {
+		self.consume_len(len)?;
+		Ok(self.consume_and_count() + len)
+	}
+
+	/// Consumes while the function returns `true`.
+	#[inline]
+	fn consume_while_byte_fn<F>(&mut self, f: F) -> &mut Self
+	where 
+		Self: Sized,
+		F: Fn(&u8) -> bool {
+		self.while_byte_fn(f).consume();
+		self
+	}
+
+	/// Consumes while the function returns `true`, but at most `max` bytes.
+	/// Returns `Err(max)` if more than `max` bytes would match, the position
+	/// is then after the first `max` bytes.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("abc def");
+	/// let word = parser
+	/// 	.record()
+	/// 	.consume_while_byte_fn_max(u8::is_ascii_alphabetic, 3).unwrap()
+	/// 	.to_str();
+	/// assert_eq!(word, "abc");
+	/// parser.advance();
+	/// assert_eq!(parser.consume_while_byte_fn_max(u8::is_ascii_alphabetic, 2).unwrap_err(), 2);
+	/// ```
+	#[inline]
+	fn consume_while_byte_fn_max<F>(
+		&mut self,
+		f: F,
+		max: usize
+	) -> Result<&mut Self, usize>
+	where F: Fn(&u8) -> bool {
+		for _ in 0..max {
+			if self.advance_if(&f) != Some(true) {
+				return Ok(self)
+			}
+		}
+
+		match self.peek().filter(&f) {
+			Some(_) => Err(max),
+			None => Ok(self)
+		}
+	}
+
+	/// Consumes while the function returns `true`, returning `true` if at
+	/// least one byte was consumed.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("12ab");
+	/// assert!(parser.advance_if_while(u8::is_ascii_digit));
+	/// assert!(!parser.advance_if_while(u8::is_ascii_digit));
+	/// assert_eq!(parser.next(), Some(b'a'));
+	/// ```
+	#[inline]
+	fn advance_if_while<F>(&mut self, f: F) -> bool
+	where
+		Self: Sized,
+		F: Fn(&u8) -> bool {
+		self.count_while_byte_fn(f) > 0
+	}
+
+	/// Consumes while the function returns `true` for the next char.
+	#[inline]
+	fn consume_while_char_fn<F>(&mut self, f: F) -> &mut Self
+	where
+		Self: Sized,
+		F: Fn(char) -> bool {
+		self.while_char_fn(f).consume();
+		self
+	}
+
+	/// Consumes while a give `byte` is returned.
+	#[inline]
+	fn consume_while_byte(&mut self, byte: u8) -> &mut Self
+	where Self: Sized {
+		self.count_byte(byte);
+		self
+	}
+
+	/// Consumes while the byte is contained in the `ByteSet`.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator, byte_set::ByteSet};
+	/// let number = ByteSet::new().digit().byte(b'_');
+	/// let s = StrParser::new("1_000 + 1")
+	/// 	.record()
+	/// 	.consume_class(&number)
+	/// 	.to_str();
+	/// assert_eq!(s, "1_000");
+	/// ```
+	#[inline]
+	fn consume_class(&mut self, class: &ByteSet) -> &mut Self
+	where Self: Sized {
+		self.consume_while_byte_fn(|&b| class.contains(b))
+	}
+
+	/// Consumes until the next byte is contained in `set` and returns it,
+	/// without consuming it. Returns `None` if the end was reached.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("key=value;");
+	/// let mut field = parser.record();
+	/// assert_eq!(field.consume_until_any_byte(b"=;"), Some(b'='));
+	/// assert_eq!(field.to_str(), "key");
+	/// ```
+	#[inline]
+	fn consume_until_any_byte(&mut self, set: &[u8]) -> Option<u8>
+	where Self: Sized {
+		let set = ByteSet::from_slice(set);
+		self.consume_while_byte_fn(|&b| !set.contains(b))
+			.peek()
+	}
+
+	/// Consumes while the byte is smaller or equal to `max` (inclusive).
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{Parser, ParseIterator};
+	/// let mut parser = Parser::new(b"\x01\x1fabc");
+	/// // consume all control bytes
+	/// parser.consume_while_byte_le(0x1f);
+	/// assert_eq!(parser.next(), Some(b'a'));
+	/// ```
+	#[inline]
+	fn consume_while_byte_le(&mut self, max: u8) -> &mut Self
+	where Self: Sized {
+		self.consume_while_byte_fn(|&b| b <= max)
+	}
+
+	/// Consumes while the byte is smaller than `max` (exclusive).
+	#[inline]
+	fn consume_while_byte_lt(&mut self, max: u8) -> &mut Self
+	where Self: Sized {
+		self.consume_while_byte_fn(|&b| b < max)
+	}
+
+	/// Consumes while the byte is bigger or equal to `min` (inclusive).
+	#[inline]
+	fn consume_while_byte_ge(&mut self, min: u8) -> &mut Self
+	where Self: Sized {
+		self.consume_while_byte_fn(|&b| b >= min)
+	}
+
+	/// Consumes while the byte is bigger than `min` (exclusive).
+	#[inline]
+	fn consume_while_byte_gt(&mut self, min: u8) -> &mut Self
+	where Self: Sized {
+		self.consume_while_byte_fn(|&b| b > min)
+	}
+
+	/// Consumes while an ascii whitespace is returned.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new(" \t\n value");
+	/// let s = parser
+	/// 	.consume_while_ascii_whitespace()
+	/// 	.record()
+	/// 	.consume_to_str();
+	/// assert_eq!(s, "value");
+	/// ```
+	#[inline]
+	fn consume_while_ascii_whitespace(&mut self) -> &mut Self
+	where Self: Sized {
+		self.consume_while_byte_fn(u8::is_ascii_whitespace)
+	}
+
+	/// Alias for `consume_while_ascii_whitespace`.
+	#[inline]
+	fn skip_whitespace(&mut self) -> &mut Self
+	where Self: Sized {
+		self.consume_while_ascii_whitespace()
+	}
+
+	/// Advances until the next bytes are equal to `needle`, without
+	/// consuming the `needle`. Returns `false` if the `needle` was not found,
+	/// the iterator is then consumed.
+	///
+	/// An empty `needle` matches immediately without advancing.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("/* a * b */ c");
+	/// parser.consume_len(2).unwrap();
+	///
+	/// let mut comment = parser.record();
+	/// assert!(comment.advance_to_slice(b"*/"));
+	/// assert_eq!(comment.to_str(), " a * b ");
+	/// ```
+	fn advance_to_slice(&mut self, needle: &[u8]) -> bool
+	where Self: Sized {
+		loop {
+			if self.starts_with(needle) {
+				return true
+			}
+
+			if self.advance().is_none() {
+				return false
+			}
+		}
+	}
+
+	/// Splits the iterator at a given byte.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("Hello World!");
+	/// let mut splitter = parser.split_on_byte(b' ');
+	///
+	/// let hello = splitter.next().unwrap()
+	/// 	.record().consume_to_str();
+	/// let world = splitter.next().unwrap()
+	/// 	.record().consume_to_str();
+	///
+	/// assert_eq!(hello, "Hello");
+	/// assert_eq!(world, "World!");
+	/// assert!(splitter.next().is_none());
+	/// ```
+	#[inline]
+	fn split_on_byte(&mut self, byte: u8) -> SplitOnByte<'_, Self>
+	where Self: Sized {
+		SplitOnByte::new(self, byte)
+	}
+
+	/// Splits the iterator into lines ending with `\n` or `\r\n`.
+	///
+	/// Like `str::lines` the line endings are not part of the lines and an
+	/// empty last line is not returned.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("a\r\nb\n");
+	/// let lines: Vec<_> = parser
+	/// 	.lines()
+	/// 	.map_and_collect(|line| line.record().consume_to_str());
+	/// assert_eq!(lines, ["a", "b"]);
+	/// ```
+	#[inline]
+	fn lines(&mut self) -> Lines<'_, Self>
+	where Self: Sized {
+		Lines::new(self)
+	}
+
+	/// Splits the iterator at a given byte, returning at most `n` segments.
+	/// The last segment contains the rest including any further `byte`.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("key=value=with=equals");
+	/// let parts: Vec<_> = parser
+	/// 	.splitn_on_byte(2, b'=')
+	/// 	.map_and_collect(|part| part.record().consume_to_str());
+	/// assert_eq!(parts, ["key", "value=with=equals"]);
+	/// ```
+	#[inline]
+	fn splitn_on_byte(&mut self, n: usize, byte: u8) -> SplitOnByte<'_, Self>
+	where Self: Sized {
+		SplitOnByte::with_limit(self, n, byte)
+	}
+
+	/// Splits the iterator at a given byte, writing up to `out.len()`
+	/// segments into `out` without allocating. Returns how many segments
+	/// were written.
+	///
+	/// Once `out` is full the rest after the `byte` following the last
+	/// segment is left unconsumed. Use `splitn_into` to merge the rest into
+	/// the last slot.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("GET /index.html HTTP/1.1");
+	/// let mut parts = [&b""[..]; 2];
+	/// assert_eq!(parser.split_into(b' ', &mut parts), 2);
+	/// assert_eq!(parts, [&b"GET"[..], b"/index.html"]);
+	/// assert_eq!(parser.record().consume_to_str(), "HTTP/1.1");
+	/// ```
+	fn split_into(&mut self, byte: u8, out: &mut [&'s [u8]]) -> usize
+	where Self: Sized {
+		fill_segments(self.split_on_byte(byte), out)
+	}
+
+	/// Like `split_into` but the last slot contains the rest including any
+	/// further `byte`, like `splitn_on_byte`.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("a,b,c,d");
+	/// let mut parts = [&b""[..]; 3];
+	/// assert_eq!(parser.splitn_into(b',', &mut parts), 3);
+	/// assert_eq!(parts, [&b"a"[..], b"b", b"c,d"]);
+	/// assert!(parser.peek().is_none());
+	/// ```
+	fn splitn_into(&mut self, byte: u8, out: &mut [&'s [u8]]) -> usize
+	where Self: Sized {
+		let n = out.len();
+		fill_segments(self.splitn_on_byte(n, byte), out)
+	}
+
+	/// Consumes the iterator and splits the consumed slice at a given byte
+	/// starting from the end.
+	///
+	/// See `rsplit_on_byte` module.
+	#[inline]
+	fn rsplit_on_byte(&mut self, byte: u8) -> RSplitOnByte<'s>
+	where Self: Sized {
+		RSplitOnByte::new(self.record().consume_to_slice(), byte)
+	}
+
+	/// Splits the rest at the last occurence of `byte`, returning the slice
+	/// before and after it.
+	///
+	/// Consumes the iterator if `byte` was found else returns `None` without
+	/// advancing.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("src/lib.rs");
+	/// let (dir, file) = parser.split_last_on_byte(b'/').unwrap();
+	/// assert_eq!(dir, b"src");
+	/// assert_eq!(file, b"lib.rs");
+	/// ```
+	#[inline]
+	fn split_last_on_byte(&mut self, byte: u8) -> Option<(&'s [u8], &'s [u8])>
+	where Self: Sized {
+		let pit = self.pit();
+		let slice = self.record().consume_to_slice();
+
+		match slice.iter().rposition(|&b| b == byte) {
+			Some(i) => Some((&slice[..i], &slice[i + 1..])),
+			None => {
+				self.restore_pit(pit);
+				None
+			}
+		}
+	}
+
+	/// Advances until the next bytes are equal to `needle`.
+	///
+	/// Returns `None` if `needle` was not found, the iterator is then empty.
+	/// An empty `needle` matches immediately without advancing.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("INFO: ok\nERROR: failed");
+	/// let line = parser
+	/// 	.skip_until_slice(b"ERROR:").unwrap()
+	/// 	.record()
+	/// 	.consume_to_str();
+	/// assert_eq!(line, "ERROR: failed");
+	/// ```
+	#[inline]
+	fn skip_until_slice(&mut self, needle: &[u8]) -> Option<&mut Self>
+	where Self: Sized {
+		if self.advance_to_slice(needle) {
+			Some(self)
+		} else {
+			None
+		}
+	}
+
+	/// Advances until after the next occurrence of `needle`.
+	///
+	/// Returns `None` if `needle` was not found, the iterator is then empty.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("INFO: ok\nERROR: failed");
+	/// let msg = parser
+	/// 	.skip_past_slice(b"ERROR: ").unwrap()
+	/// 	.record()
+	/// 	.consume_to_str();
+	/// assert_eq!(msg, "failed");
+	/// ```
+	#[inline]
+	fn skip_past_slice(&mut self, needle: &[u8]) -> Option<&mut Self>
+	where Self: Sized {
+		self.skip_until_slice(needle)?;
+		// the needle was found so this can't fail
+		self.consume_len(needle.len()).ok()
+	}
+
+	/// Splits the iterator at a given slice.
+	///
+	/// Works like `split_on_byte` but every segment ends before the `needle`.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("a-->b-->c");
+	/// let parts: Vec<_> = parser
+	/// 	.split_on_slice(b"-->")
+	/// 	.map_and_collect(|part| part.record().consume_to_str());
+	///
+	/// assert_eq!(parts, ["a", "b", "c"]);
+	/// ```
+	///
+	/// ## Panics
+	/// If the `needle` is empty.
+	#[inline]
+	fn split_on_slice<'a>(&'a mut self, needle: &'a [u8]) -> SplitOnSlice<'a, Self>
+	where Self: Sized {
+		SplitOnSlice::new(self, needle)
+	}
+
+	/// Splits the iterator at any byte contained in `bytes`.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("a=1;b=2");
+	/// let mut split = parser.split_on_any_byte(b"=;");
+	///
+	/// let a = split.next().unwrap();
+	/// assert_eq!(a.record().consume_to_str(), "a");
+	/// assert_eq!(a.delimiter(), Some(b'='));
+	/// ```
+	#[inline]
+	fn split_on_any_byte(&mut self, bytes: &[u8]) -> SplitOnAnyByte<'_, Self>
+	where Self: Sized {
+		SplitOnAnyByte::new(self, bytes)
+	}
+
+	/// Splits the iterator at every byte for which `f` returns `true`.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("a b\nc");
+	/// let parts: Vec<_> = parser
+	/// 	.split_on_fn(u8::is_ascii_whitespace)
+	/// 	.map_and_collect(|part| part.record().consume_to_str());
+	///
+	/// assert_eq!(parts, ["a", "b", "c"]);
+	/// ```
+	#[inline]
+	fn split_on_fn<F>(&mut self, f: F) -> SplitOnFn<'_, Self, F>
+	where
+		Self: Sized,
+		F: Fn(&u8) -> bool {
+		SplitOnFn::new(self, f)
+	}
+
+	/// Splits the iterator at every `byte` which is not inside a pair of
+	/// `quote`s. The quotes are part of the segments.
+	///
+	/// A doubled quote inside quotes is treated as an escaped quote, which
+	/// makes this suitable for csv fields.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("name,\"Doe, John\"");
+	/// let fields: Vec<_> = parser
+	/// 	.split_on_byte_respecting(b',', b'"')
+	/// 	.map_and_collect(|field| field.record().consume_to_str());
+	///
+	/// assert_eq!(fields, ["name", "\"Doe, John\""]);
+	/// ```
+	#[inline]
+	fn split_on_byte_respecting(
+		&mut self,
+		byte: u8,
+		quote: u8
+	) -> SplitRespecting<'_, Self>
+	where Self: Sized {
+		SplitRespecting::new(self, byte, quote)
+	}
+
+	/// Splits the iterator at a given byte and maps every segment with `f`,
+	/// collecting the results. Stops at the first error and returns it.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let nums: Result<Vec<u32>, _> = StrParser::new("1,2,3")
+	/// 	.try_split_collect(b',', |seg| {
+	/// 		seg.record().consume_to_str().parse::<u32>()
+	/// 	});
+	/// assert_eq!(nums.unwrap(), [1, 2, 3]);
+	///
+	/// let nums: Result<Vec<u32>, _> = StrParser::new("1,a,3")
+	/// 	.try_split_collect(b',', |seg| {
+	/// 		seg.record().consume_to_str().parse::<u32>()
+	/// 	});
+	/// assert!(nums.is_err());
+	/// ```
+	fn try_split_collect<F, T, E, B>(&mut self, byte: u8, mut f: F) -> Result<B, E>
+	where
+		Self: Sized,
+		F: FnMut(&mut SplitOnByteIter<'_, Self>) -> Result<T, E>,
+		B: core::iter::FromIterator<T> {
+		let mut split = self.split_on_byte(byte);
+		core::iter::from_fn(|| {
+			split.next().map(&mut f)
+		})
+		.collect()
+	}
+
+	/// Returns an iterator over `KEY=VALUE` lines (like in a `.env` file),
+	/// yielding the trimmed key and value.
+	///
+	/// Lines can end with `\n` or `\r\n`, blank lines and lines starting with
+	/// a `#` are skipped. Every line is split once on `kv_sep`, if a line does
+	/// not contain `kv_sep` it is returned as a key with an empty value.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("# comment\nA=1\r\nB = 2\n");
+	/// let pairs: Vec<_> = parser.parse_kv_lines(b'=').collect();
+	/// assert_eq!(pairs, [("A", "1"), ("B", "2")]);
+	/// ```
+	///
+	/// ## Panics
+	/// Panics if a line contains invalid utf8.
+	#[inline]
+	fn parse_kv_lines(&mut self, kv_sep: u8) -> KeyValues<'s, '_, Self>
+	where Self: Sized {
+		KeyValues::lines(self, kv_sep)
+	}
+
+	/// Returns an iterator over entries separated by `pair_sep`, yielding
+	/// the trimmed key and value of every entry.
+	///
+	/// Entries with an empty key are skipped. Every entry is split once on
+	/// `kv_sep`, if an entry does not contain `kv_sep` it is returned as a
+	/// key with an empty value.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// # use std::collections::HashMap;
+	/// let mut parser = StrParser::new("a=1; b=2; flag");
+	/// let map: HashMap<_, _> = parser.split_key_value(b';', b'=').collect();
+	/// assert_eq!(map["b"], "2");
+	/// assert_eq!(map["flag"], "");
+	/// ```
+	///
+	/// ## Panics
+	/// Panics if an entry contains invalid utf8.
+	#[inline]
+	fn split_key_value(
+		&mut self,
+		pair_sep: u8,
+		kv_sep: u8
+	) -> KeyValues<'s, '_, Self>
+	where Self: Sized {
+		KeyValues::pairs(self, pair_sep, kv_sep)
+	}
+
+	/// Returns `true` if `byte` is contained in the rest of the iterator,
+	/// without advancing.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("a,b;c");
+	/// let mut split = parser.split_on_byte(b';');
+	/// let field = split.next().unwrap();
+	/// assert!(field.contains_byte(b','));
+	/// // the split byte is not part of the segment
+	/// assert!(!field.contains_byte(b';'));
+	/// assert_eq!(field.next(), Some(b'a'));
+	/// ```
+	#[inline]
+	fn contains_byte(&mut self, byte: u8) -> bool {
+		if unsafe { Self::advances_by_one() } {
+			return find_byte(byte, self.remaining()).is_some()
+		}
+
+		let pit = self.pit();
+		let mut found = false;
+		while let Some(b) = self.next() {
+			if b == byte {
+				found = true;
+				break
+			}
+		}
+		self.restore_pit(pit);
+		found
+	}
+
+	#[inline]
+	fn count_byte(&mut self, byte: u8) -> usize
+	where Self: Sized {
+		if unsafe { Self::advances_by_one() } {
+			let rest = self.remaining();
+			let len = find_not_byte(byte, rest).unwrap_or(rest.len());
+			// safe because advances_by_one returned true
+			unsafe { skip_unchecked(self, len) };
+			return len
+		}
+
+		self.while_byte_fn(|&b| b == byte)
+			.consume_and_count()
+	}
+
+	/// Consumes until the iterator is empty, returning how many of the
+	/// consumed bytes are contained in `set`.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("a\r\nb\nc");
+	/// assert_eq!(parser.count_bytes(b"\r\n"), 3);
+	/// assert!(parser.next().is_none());
+	/// ```
+	#[inline]
+	fn count_bytes(&mut self, set: &[u8]) -> usize
+	where Self: Sized {
+		let set = ByteSet::from_slice(set);
+		let mut count = 0;
+		while let Some(b) = self.next() {
+			if set.contains(b) {
+				count += 1;
+			}
+		}
+		count
+	}
+
+	/// Consumes until the iterator is empty, counting every byte in `bytes`
+	/// separately. The counts are returned in the same order as `bytes`.
+	/// If a byte is contained multiple times only the first one is counted.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("\tif a:\n\t\treturn\n  b");
+	/// let [tabs, spaces] = parser.scan_counts([b'\t', b' ']);
+	/// assert_eq!((tabs, spaces), (3, 3));
+	/// ```
+	#[inline]
+	fn scan_counts<const N: usize>(&mut self, bytes: [u8; N]) -> [usize; N]
+	where Self: Sized {
+		// maps every byte to its index in bytes
+		let mut table = [None; 256];
+		for (i, &b) in bytes.iter().enumerate().rev() {
+			table[b as usize] = Some(i);
+		}
+
+		let mut counts = [0; N];
+		while let Some(b) = self.next() {
+			if let Some(i) = table[b as usize] {
+				counts[i] += 1;
+			}
+		}
+		counts
+	}
+
+	/// Counts how many consecutive bytes `f` returns `true` for
+	/// and advances past them.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("    indented");
+	/// let depth = parser.count_while_byte_fn(|&b| b == b' ');
+	/// assert_eq!(depth, 4);
+	/// assert_eq!(parser.next(), Some(b'i'));
+	/// ```
+	#[inline]
+	fn count_while_byte_fn<F>(&mut self, f: F) -> usize
+	where
+		Self: Sized,
+		F: Fn(&u8) -> bool {
+		self.while_byte_fn(f)
+			.consume_and_count()
+	}
+
+	/// Counts how many consecutive bytes `f` returns `true` for,
+	/// without advancing the internal position.
+	#[inline]
+	fn peek_count_while_byte_fn<F>(&mut self, f: F) -> usize
+	where
+		Self: Sized,
+		F: Fn(&u8) -> bool {
+		let pit = self.pit();
+		let count = self.count_while_byte_fn(f);
+		self.restore_pit(pit);
+		count
+	}
+
+	/// Starts a new `Recorder` which starts recording at this position.
+	#[inline]
+	fn record(&mut self) -> RecordIter<'_, Self>
+	where Self: Sized {
+		RecordIter::new(self)
+	}
+
+	/// Returns a slice from the start of recording until now.
+	///
+	/// ## Panics
+	/// If not called in context of a recorder. Meaning before
+	/// calling `record`.
+	#[inline]
+	fn to_slice(&self) -> &'s [u8] {
+		self.try_to_slice().expect("no recorder found")
+	}
+
+	/// Returns a slice from the start of recording until now, or `None` if
+	/// `record` was not called.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("abc");
+	/// assert!(parser.try_to_slice().is_none());
+	/// assert_eq!(parser.record().consume().try_to_slice(), Some(&b"abc"[..]));
+	/// ```
+	#[inline]
+	fn try_to_slice(&self) -> Option<&'s [u8]> {
+		let recorder = self.recorder()?;
+		let start = recorder.pos() + 1;
+		let end = recorder.end(self.pit().record_pos());
+
+		Some(&self.slice()[start..end])
+	}
+
+	/// Returns how many bytes were recorded, which is the same as
+	/// `to_slice().len()`.
+	///
+	/// ## Panics
+	/// If not called in context of a recorder. Meaning before
+	/// calling `record`.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("a_very_long_token");
+	/// let mut token = parser.record();
+	/// while token.next_if(|&b| b != b' ').is_some() {
+	/// 	if token.recorded_len() > 4 {
+	/// 		break
+	/// 	}
+	/// }
+	/// assert_eq!(token.to_str(), "a_ver");
+	/// ```
+	#[inline]
+	fn recorded_len(&self) -> usize {
+		let recorder = self.recorder().expect("no recorder found");
+		let start = recorder.pos() + 1;
+		let end = recorder.end(self.pit().record_pos());
+
+		end - start
+	}
+
+	/// Returns the slice between two previously captured points in time,
+	/// without needing a recorder.
+	///
+	/// ## Panics
+	/// Panics if `start` is after `end`.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("key=value");
+	/// let start = parser.pit();
+	/// parser.consume_while_byte_fn(|&b| b != b'=');
+	/// let end = parser.pit();
+	/// parser.consume();
+	/// assert_eq!(parser.slice_between(start, end), b"key");
+	/// ```
+	#[inline]
+	fn slice_between(
+		&self,
+		start: Self::PointInTime,
+		end: Self::PointInTime
+	) -> &'s [u8] {
+		let start = start.pos() + 1;
+		let end = end.record_pos() + 1;
+		assert!(start <= end, "slice_between start is after end");
+
+		&self.slice()[start..end]
+	}
+
+	/// Returns a `str` from the start of recording until the current position
+	/// without checking if the data is valid utf8.
+	/// ## Panics
+	/// Panics if not called after `record` gets called.
+	/// ## Safety
+	/// This function is safe if `Self::is_valid_utf8` returns `true`.
+	#[inline]
+	unsafe fn to_str_unchecked(&self) -> &'s str {
+		core::str::from_utf8_unchecked(self.to_slice())
+	}
+
+	/// ## Safety
+	/// Returning `false` is always safe. 
+	/// If you return `true` the entire underlying slice must be valid utf8.
+	unsafe fn is_valid_utf8() -> bool;
+
+	/// Allows adapters to skip calling `advance` for every byte and
+	/// instead search the underlying slice directly.
+	///
+	/// ## Safety
+	/// Returning `false` is always safe.
+	/// If you return `true`, `advance` must move exactly one byte forward
+	/// and only return `None` at the end of the slice. Restoring a point in
+	/// time with any position in the slice must be valid.
+	#[doc(hidden)]
+	#[inline]
+	unsafe fn advances_by_one() -> bool {
+		false
+	}
+
+	/// Returns a `str` from the start of recording until the current position.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{Parser, StrParser, ParseIterator};
+	/// let str_from_slice = Parser::new(b"abc")
+	///		.record()
+	/// 	.consume()
+	/// 	.to_str();
+	/// assert_eq!(str_from_slice, "abc");
+	///
+	/// let str_from_str = StrParser::new("abc")
+	/// 	.record()
+	/// 	.consume()
+	/// 	.to_str();
+	/// assert_eq!(str_from_str, "abc");
+	/// ```
+	///
+	/// ## Panics
+	/// Panics if not called after `record` was called. 
+	/// Or if invalid utf8 is present.
+	#[inline]
+	fn to_str(&self) -> &'s str {
+		if unsafe { Self::is_valid_utf8() } {
+			// Safe because is_valid_utf8 guaranties everything is valid utf8
+			unsafe { self.to_str_unchecked() }
+		} else {
+			core::str::from_utf8(self.to_slice()).expect("invalid utf8")
+		}
+	}
+
+	/// Returns a `str` from the start of recording until the current position.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{Parser, StrParser, ParseIterator};
+	/// let str_from_slice = Parser::new(b"abc")
+	///		.record()
+	/// 	.consume()
+	/// 	.try_to_str().expect("slice is not valid utf8");
+	/// assert_eq!(str_from_slice, "abc");
+	///
+	/// let str_from_str = StrParser::new("abc")
+	/// 	.record()
+	/// 	.consume()
+	/// 		// can never return Err
+	/// 	.try_to_str().unwrap();
+	/// assert_eq!(str_from_str, "abc");
+	/// ```
+	///
+	/// ## Panics
+	/// Panics if not called after `record` was called.
+	#[inline]
+	fn try_to_str(&self) -> Result<&'s str, core::str::Utf8Error> {
+		if unsafe { Self::is_valid_utf8() } {
+			// Safe because is_valid_utf8 guaranties everything is valid utf8
+			Ok(unsafe { self.to_str_unchecked() })
+		} else {
+			core::str::from_utf8(self.to_slice())
+		}
+	}
+
+	/// Returns a `str` from the start of recording until the current position.
+	/// Returns `None` if `record` was not called or if invalid utf8 is
+	/// present.
+	///
+	/// Unlike `try_to_str` this never panics.
+	#[inline]
+	fn try_to_str_opt(&self) -> Option<&'s str> {
+		let slice = self.try_to_slice()?;
+		if unsafe { Self::is_valid_utf8() } {
+			// Safe because is_valid_utf8 guaranties everything is valid utf8
+			Some(unsafe { core::str::from_utf8_unchecked(slice) })
+		} else {
+			core::str::from_utf8(slice).ok()
+		}
+	}
+
+	/// Returns a `str` from the start of recording until the current position,
+	/// replacing invalid utf8 with `U+FFFD`.
+	///
+	/// Only allocates if invalid utf8 was found.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{Parser, ParseIterator};
+	/// let mut parser = Parser::new(b"log \xff line\nnext");
+	/// let line = parser
+	/// 	.split_on_byte(b'\n')
+	/// 	.next().unwrap()
+	/// 	.record()
+	/// 	.consume()
+	/// 	.to_str_lossy();
+	/// assert_eq!(line, "log \u{FFFD} line");
+	/// ```
+	///
+	/// ## Panics
+	/// Panics if not called after `record` was called.
+	#[cfg(feature = "alloc")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+	#[inline]
+	fn to_str_lossy(&self) -> Cow<'s, str> {
+		if unsafe { Self::is_valid_utf8() } {
+			// Safe because is_valid_utf8 guaranties everything is valid utf8
+			Cow::Borrowed(unsafe { self.to_str_unchecked() })
+		} else {
+			String::from_utf8_lossy(self.to_slice())
+		}
+	}
+
+	/// Consumes the iterator and then returns a slice from the start of recording
+	/// until the current position.
+	///
+	/// ## Panics
+	/// Panics if not called after `record` was called.
+	#[inline]
+	fn consume_to_slice(&mut self) -> &'s [u8] {
+		self.consume().to_slice()
+	}
+
+	/// Consumes the iterator and then returns a str from the start of recording
+	/// until the current position. Without checking if the underlying data
+	/// is valid utf8.
+	///
+	/// ## Panics
+	/// Panics if not called after `record` was called.
+	#[inline]
+	unsafe fn consume_to_str_unchecked(&mut self) -> &'s str {
+		self.consume().to_str_unchecked()
+	}
+
+	/// Consumes the iterator and then returns a str from the start of recording
+	/// until the current position.
+	///
+	/// ## Panics
+	/// Panics if not called after `record` was called or if the data contains invalid
+	/// utf8.
+	#[inline]
+	fn consume_to_str(&mut self) -> &'s str {
+		self.consume().to_str()
+	}
+
+	/// Consumes the iterator and then returns a str from the start of recording
+	/// until the current position if the data is valid utf8.
+	///
+	/// ## Panics
+	/// Panics if not called after `record` was called.
+	#[inline]
+	fn consume_try_to_str(&mut self) -> Result<&'s str, core::str::Utf8Error> {
+		self.consume().try_to_str()
+	}
+
+	/// Consumes the iterator and then returns an owned copy of the bytes from
+	/// the start of recording until the current position.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("abc");
+	/// let v = parser.record().consume_to_vec();
+	/// assert_eq!(v, b"abc".to_vec());
+	/// ```
+	///
+	/// ## Panics
+	/// Panics if not called after `record` was called.
+	#[cfg(feature = "alloc")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+	#[inline]
+	fn consume_to_vec(&mut self) -> Vec<u8> {
+		self.consume_to_slice().to_vec()
+	}
+
+	/// Consumes the iterator and then returns an owned copy of the str from
+	/// the start of recording until the current position.
+	///
+	/// ## Panics
+	/// Panics if not called after `record` was called or if the data contains
+	/// invalid utf8.
+	#[cfg(feature = "alloc")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+	#[inline]
+	fn consume_to_string(&mut self) -> String {
+		self.consume_to_str().into()
+	}
+
+	/// Consumes the iterator and then returns a str from the start of recording
+	/// until the current position, replacing invalid utf8 with `U+FFFD`.
+	///
+	/// ## Panics
+	/// Panics if not called after `record` was called.
+	#[cfg(feature = "alloc")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+	#[inline]
+	fn consume_to_str_lossy(&mut self) -> Cow<'s, str> {
+		self.consume().to_str_lossy()
+	}
+
+	/// Consumes the iterator passing every byte to `f`. If `f` returns
+	/// `Some` the returned byte gets pushed to `buf`, `None` drops it.
+	///
+	/// `buf` is not cleared so it can be reused between calls.
+	///
+	/// Does not require `record` to be called before.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new(r"a\\b\,c");
+	/// let mut buf = vec![];
+	///
+	/// let mut escaped = false;
+	/// parser.consume_map_bytes(&mut buf, |b| {
+	/// 	if !escaped && b == b'\\' {
+	/// 		escaped = true;
+	/// 		return None
+	/// 	}
+	/// 	escaped = false;
+	/// 	Some(b)
+	/// });
+	/// assert_eq!(buf, br"a\b,c");
+	/// ```
+	#[cfg(feature = "alloc")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+	#[inline]
+	fn consume_map_bytes<F>(&mut self, buf: &mut Vec<u8>, mut f: F)
+	where F: FnMut(u8) -> Option<u8> {
+		while let Some(b) = self.next() {
+			if let Some(b) = f(b) {
+				buf.push(b);
+			}
+		}
+	}
+
+	/// Consumes while `f` returns `true` and returns the consumed slice.
+	///
+	/// Does not require `record` to be called before.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{Parser, ParseIterator};
+	/// let mut parser = Parser::new(b"123abc");
+	/// assert_eq!(parser.take_while_byte_fn(u8::is_ascii_digit), b"123");
+	/// assert_eq!(parser.take_while_byte_fn(u8::is_ascii_digit), b"");
+	/// assert_eq!(parser.next(), Some(b'a'));
+	/// ```
+	#[inline]
+	fn take_while_byte_fn<F>(&mut self, f: F) -> &'s [u8]
+	where
+		Self: Sized,
+		F: Fn(&u8) -> bool {
+		self.record()
+			.while_byte_fn(f)
+			.consume_to_slice()
+	}
+
+	/// Consumes while `f` returns `true` and returns the consumed str.
+	///
+	/// Does not require `record` to be called before.
+	///
+	/// ## Panics
+	/// Panics if the data contains invalid utf8.
+	#[inline]
+	fn take_while_byte_fn_str<F>(&mut self, f: F) -> &'s str
+	where
+		Self: Sized,
+		F: Fn(&u8) -> bool {
+		self.record()
+			.while_byte_fn(f)
+			.consume_to_str()
+	}
+
+	/// Consumes until `byte` and returns the consumed slice without `byte`.
+	/// The next call to `next` returns `byte`.
+	///
+	/// If `byte` is not found the rest gets returned.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{Parser, ParseIterator};
+	/// let mut parser = Parser::new(b"key=value");
+	/// assert_eq!(parser.take_until_byte(b'='), b"key");
+	/// assert_eq!(parser.next(), Some(b'='));
+	/// ```
+	#[inline]
+	fn take_until_byte(&mut self, byte: u8) -> &'s [u8]
+	where Self: Sized {
+		self.take_while_byte_fn(|&b| b != byte)
+	}
+
+	/// Consumes until and including `byte` and returns the consumed slice
+	/// with `byte`.
+	///
+	/// If `byte` is not found the rest gets returned.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{Parser, ParseIterator};
+	/// let mut parser = Parser::new(b"key=value");
+	/// assert_eq!(parser.take_through_byte(b'='), b"key=");
+	/// assert_eq!(parser.next(), Some(b'v'));
+	/// ```
+	#[inline]
+	fn take_through_byte(&mut self, byte: u8) -> &'s [u8]
+	where Self: Sized {
+		let mut rec = self.record();
+		rec.consume_while_byte_fn(|&b| b != byte);
+		// either byte or the end
+		rec.advance();
+		rec.to_slice()
+	}
+
+	/// Same as `take_until_byte` but returns a str.
+	///
+	/// ## Panics
+	/// Panics if the data contains invalid utf8.
+	#[inline]
+	fn take_until_byte_str(&mut self, byte: u8) -> &'s str
+	where Self: Sized {
+		self.take_while_byte_fn_str(|&b| b != byte)
+	}
+
+	/// Same as `take_through_byte` but returns a str.
+	///
+	/// ## Panics
+	/// Panics if the data contains invalid utf8.
+	#[inline]
+	fn take_through_byte_str(&mut self, byte: u8) -> &'s str
+	where Self: Sized {
+		let mut rec = self.record();
+		rec.consume_while_byte_fn(|&b| b != byte);
+		rec.advance();
+		rec.to_str()
+	}
+
+	/// Returns ```&mut Self``` if the function returns `true` on the next byte.
+	/// Else returns the byte that was received.
+	#[inline]
+	fn expect_byte_fn<F>(&mut self, f: F) -> Result<&mut Self, Option<u8>>
+	where F: Fn(u8) -> bool {
+		self.next()
+			.expect_byte_fn(f)
+			.map(|_| self)
+	}
+
+	/// Returns ```&mut Self``` if the function byte is equal to the next byte.
+	/// Else returns the actual byte that was received.
+	#[inline]
+	fn expect_byte(&mut self, byte: u8) -> Result<&mut Self, Option<u8>> {
+		self.expect_byte_fn(|b| b == byte)
+	}
+
+	/// Returns the next byte if it is contained in `set`. Else returns the
+	/// actual byte that was received and does not advance.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("-1");
+	/// assert_eq!(parser.expect_one_of(b"+-"), Ok(b'-'));
+	/// assert_eq!(parser.expect_one_of(b"+-"), Err(Some(b'1')));
+	/// assert_eq!(parser.next(), Some(b'1'));
+	/// assert_eq!(parser.expect_one_of(b"+-"), Err(None));
+	/// ```
+	#[inline]
+	fn expect_one_of(&mut self, set: &[u8]) -> Result<u8, Option<u8>> {
+		let set = ByteSet::from_slice(set);
+		match self.peek() {
+			Some(b) if set.contains(b) => {
+				self.advance();
+				Ok(b)
+			},
+			b => Err(b)
+		}
+	}
+
+	/// Returns ```&mut Self``` if the next bytes are equal to `bytes`.
+	///
+	/// The expectation is atomic, if any byte does not match the position
+	/// is restored to where it was before calling this method.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{Parser, ParseIterator};
+	/// let mut parser = Parser::new(b"abd");
+	/// let err = parser.expect_bytes(b"abc").unwrap_err();
+	/// assert_eq!(err.position(), 2);
+	/// assert_eq!(err.found(), Some(b'd'));
+	/// // nothing was consumed
+	/// assert_eq!(parser.next(), Some(b'a'));
+	/// ```
+	fn expect_bytes(&mut self, bytes: &[u8]) -> Result<&mut Self, ParseError> {
+		let pit = self.pit();
+
+		for &byte in bytes {
+			let position = self.offset();
+			match self.next() {
+				Some(b) if b == byte => {},
+				found => {
+					let position = match found {
+						Some(_) => self.pit().pos().opt().unwrap_or(position),
+						None => position
+					};
+					self.restore_pit(pit);
+					return Err(ParseError::new(position, found))
+				}
+			}
+		}
+
+		Ok(self)
+	}
+
+	/// Returns ```&mut Self``` if the next byte is equal to `byte`.
+	/// Else returns a `ParseError` labeled with `expected`, the position
+	/// is then not changed.
+	#[inline]
+	fn expect_byte_err(
+		&mut self,
+		byte: u8,
+		expected: &'static str
+	) -> Result<&mut Self, ParseError> {
+		self.expect_slice_err(&[byte], expected)
+	}
+
+	/// Returns ```&mut Self``` if the next bytes are equal to `s`.
+	/// Else returns a `ParseError` labeled with `expected`, the position
+	/// is then restored.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("SELECT *");
+	/// let err = parser.expect_slice_err(b"SET", "keyword SET").unwrap_err();
+	/// assert_eq!(err.position(), 2);
+	/// assert_eq!(err.expected(), Some("keyword SET"));
+	/// ```
+	#[inline]
+	fn expect_slice_err(
+		&mut self,
+		s: &[u8],
+		expected: &'static str
+	) -> Result<&mut Self, ParseError> {
+		self.expect_bytes(s)
+			.map_err(|e| e.with_expected(expected))
+	}
+
+	/// Returns ```&mut Self``` if the next bytes are equal to `s`.
+	/// Else returns how many bytes matched, the position is then restored.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("SELECT *");
+	/// assert_eq!(parser.expect_slice(b"SET").unwrap_err(), 2);
+	/// assert!(parser.expect_slice(b"SELECT").is_ok());
+	/// assert_eq!(parser.next(), Some(b' '));
+	/// ```
+	#[inline]
+	fn expect_slice(&mut self, s: &[u8]) -> Result<&mut Self, usize>
+	where Self: Sized {
+		expect_slice_with(self, s, |a, b| a == b)?;
+		Ok(self)
+	}
+
+	/// Advances over `s` if the next bytes are equal to `s`.
+	/// Returns `false` and does not advance if they are not equal.
+	#[inline]
+	fn advance_if_slice(&mut self, s: &[u8]) -> bool
+	where Self: Sized {
+		self.expect_slice(s).is_ok()
+	}
+
+	/// Returns ```&mut Self``` if the next bytes are equal to `s` ignoring
+	/// the ascii case. Else returns how many bytes matched, the position
+	/// is then restored.
+	///
+	/// The recorded slice contains the actual bytes and not `s`.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("content-LENGTH: 2");
+	/// let header = parser
+	/// 	.record()
+	/// 	.expect_slice_ignore_ascii_case(b"Content-Length").unwrap()
+	/// 	.to_str();
+	/// assert_eq!(header, "content-LENGTH");
+	/// ```
+	#[inline]
+	fn expect_slice_ignore_ascii_case(&mut self, s: &[u8]) -> Result<&mut Self, usize>
+	where Self: Sized {
+		expect_slice_with(self, s, |a, b| a.eq_ignore_ascii_case(&b))?;
+		Ok(self)
+	}
+
+	/// Advances over `s` if the next bytes are equal to `s` ignoring the
+	/// ascii case. Returns `false` and does not advance if they are
+	/// not equal.
+	#[inline]
+	fn advance_if_slice_ignore_ascii_case(&mut self, s: &[u8]) -> bool
+	where Self: Sized {
+		self.expect_slice_ignore_ascii_case(s).is_ok()
+	}
+
+	/// Returns ```&mut Self``` if the end was reached (next returns None).
+	#[inline]
+	fn expect_none(&mut self) -> Result<&mut Self, u8> {
+		match self.next() {
+			Some(b) => Err(b),
+			None => Ok(self)
+		}
+	}
+
+	/// Returns a `ParseIterator` that always returns None.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut s = StrParser::new("abc");
+	/// assert_eq!(b'a', s.next().unwrap());
+	/// let mut s = s.stop();
+	/// assert!(s.next().is_none());
+	/// ```
+	#[inline]
+	fn stop(&mut self) -> Stop<'_, Self>
+	where Self: Sized {
+		Stop::new(self)
+	}
+
+	/// Returns a `ParseIterator` that returns `None` after `n` bytes.
+	///
+	/// The position is shared with `self`, so everything consumed by the
+	/// returned iterator is also consumed from `self`.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("\x03abcdef");
+	/// let len = parser.next().unwrap() as usize;
+	/// let payload = parser.take(len).record().consume_to_str();
+	/// assert_eq!(payload, "abc");
+	/// assert_eq!(parser.next(), Some(b'd'));
+	/// ```
+	#[inline]
+	fn take(&mut self, n: usize) -> Take<'_, Self>
+	where Self: Sized {
+		Take::new(self, n)
+	}
+
+	/// Calls `f` with an iterator limited to the next `limit` bytes (see
+	/// `take`). Afterwards all `limit` bytes are consumed, no matter how
+	/// many `f` consumed.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// // length prefixed records
+	/// let mut parser = StrParser::new("\x04ab;c\x01d");
+	/// let mut records = vec![];
+	/// while let Some(len) = parser.next() {
+	/// 	let record = parser.scoped(len as usize, |rec| {
+	/// 		// only reads until the ; even though the record is longer
+	/// 		rec.take_while_byte_fn_str(|&b| b != b';')
+	/// 	});
+	/// 	records.push(record);
+	/// }
+	/// assert_eq!(records, ["ab", "d"]);
+	/// ```
+	#[inline]
+	fn scoped<F, O>(&mut self, limit: usize, f: F) -> O
+	where
+		Self: Sized,
+		F: FnOnce(&mut Take<'_, Self>) -> O {
+		let mut take = self.take(limit);
+		let o = f(&mut take);
+		take.consume();
+		o
+	}
+
+	/// Returns an `Iterator` over the remaining bytes, so the standard
+	/// iterator adapters can be used.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("a1b2");
+	/// let sum: u32 = parser.bytes()
+	/// 	.filter(u8::is_ascii_digit)
+	/// 	.map(|b| (b - b'0') as u32)
+	/// 	.sum();
+	/// assert_eq!(sum, 3);
+	/// ```
+	#[inline]
+	fn bytes(&mut self) -> Bytes<'_, Self>
+	where Self: Sized {
+		Bytes::new(self)
+	}
+
+	/// Returns an `Iterator` over the offsets of all bytes for which `f`
+	/// returns `true`. The offsets are absolute in the underlying slice,
+	/// like `offset`.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let input = "ab,cd,e";
+	/// let mut parser = StrParser::new(input);
+	/// let commas: Vec<_> = parser.positions_where(|&b| b == b',').collect();
+	/// assert_eq!(commas, [2, 5]);
+	/// assert_eq!(&input[commas[0] + 1..commas[1]], "cd");
+	/// ```
+	#[inline]
+	fn positions_where<F>(&mut self, f: F) -> Positions<'_, Self, F>
+	where
+		Self: Sized,
+		F: Fn(&u8) -> bool {
+		Positions::new(self, f)
+	}
+
+	/// Returns a guard which panics in debug builds when dropped if the
+	/// iterator was not fully consumed. In release builds this does nothing.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("42");
+	/// let mut parser = parser.guard_full_consumption();
+	/// let num = parser
+	/// 	.record()
+	/// 	.consume_while_byte_fn(u8::is_ascii_digit)
+	/// 	.to_str();
+	/// assert_eq!(num, "42");
+	/// // if something would remain the drop would panic
+	/// ```
+	#[inline]
+	fn guard_full_consumption(&mut self) -> FullConsumptionGuard<'s, '_, Self>
+	where Self: Sized {
+		FullConsumptionGuard::new(self)
+	}
+
+	/// Returns a guard which restores the current position when dropped,
+	/// unless `commit` is called on it.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("-a");
+	///
+	/// {
+	/// 	let mut cp = parser.checkpoint();
+	/// 	let num = cp.expect_byte(b'-').ok()
+	/// 		.and_then(|p| p.next())
+	/// 		.filter(u8::is_ascii_digit);
+	/// 	if num.is_some() {
+	/// 		cp.commit();
+	/// 	}
+	/// }
+	/// // the checkpoint was dropped and restored the position
+	/// assert_eq!(parser.next(), Some(b'-'));
+	/// ```
+	#[inline]
+	fn checkpoint(&mut self) -> Checkpoint<'s, '_, Self>
+	where Self: Sized {
+		Checkpoint::new(self)
+	}
+
+	/// Generates an `Iterator` which calls `f` until it returns `None`.
+	///
+	/// This is the method version of `parse_iter`.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let words: Vec<_> = StrParser::new("my byte str")
+	/// 	.into_iter_with(|parser| {
+	/// 		let mut split = parser.split_on_byte(b' ');
+	/// 		split.next()
+	/// 			.map(|n| n.record().consume_to_str())
+	/// 	})
+	/// 	.collect();
+	/// assert_eq!(words, ["my", "byte", "str"]);
+	/// ```
+	#[cfg(feature = "unstable-parse-iter")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "unstable-parse-iter")))]
+	#[inline]
+	fn into_iter_with<F, O>(self, f: F) -> ParseIter<Self, F>
+	where
+		Self: Sized,
+		F: FnMut(&mut Self) -> Option<O> {
+		ParseIter::new(self, f)
+	}
+
+}
+
+/// Advances over `s` if every byte is equal according to `eq`.
+/// Else restores the position and returns how many bytes matched.
+fn expect_slice_with<'s, I, F>(iter: &mut I, s: &[u8], eq: F) -> Result<(), usize>
+where
+	I: ParseIterator<'s> + ?Sized,
+	F: Fn(u8, u8) -> bool {
+	let pit = iter.pit();
+
+	for (i, &expected) in s.iter().enumerate() {
+		match iter.next() {
+			Some(b) if eq(b, expected) => {},
+			_ => {
+				iter.restore_pit(pit);
+				return Err(i)
+			}
+		}
+	}
+
+	Ok(())
+}
+
+/// Writes the segments of `split` into `out` until it is full and returns
+/// how many were written.
+fn fill_segments<'s, T>(mut split: SplitOnByte<'_, T>, out: &mut [&'s [u8]]) -> usize
+where T: ParseIterator<'s> {
+	let mut count = 0;
+	for slot in out.iter_mut() {
+		match split.next() {
+			Some(segment) => *slot = segment.record().consume_to_slice(),
+			None => break
+		}
+		count += 1;
+	}
+	count
+}
+
+/// Moves `len` bytes forward without calling `advance`.
+///
+/// ## Safety
+/// `advances_by_one` needs to return `true` and `len` can't go past the end
+/// of the slice.
+#[inline]
+pub(crate) unsafe fn skip_unchecked<'s, I>(iter: &mut I, len: usize)
+where I: ParseIterator<'s> + ?Sized {
+	if len == 0 {
+		return
+	}
+
+	let mut pit = iter.pit();
+	pit.set_pos((pit.pos() + len).into());
+	iter.restore_pit(pit);
+}
+
+/// Returns the length of a utf8 char based on the first byte.
+/// Invalid first bytes return 1.
+#[inline]
+pub(crate) fn utf8_char_width(first: u8) -> usize {
+	match first {
+		0xC0..=0xDF => 2,
+		0xE0..=0xEF => 3,
+		0xF0..=0xF7 => 4,
+		_ => 1
+	}
+}
+
+#[cfg(test)]
+mod tests {
+
+	use crate::*;
+
+	#[test]
+	fn test_count_byte() {
+
+		let s = b"baaaab";
+
+		let mut parser = Parser::new( s );
+		assert_eq!( 0, parser.count_byte(b'a') );
+		assert_eq!( b'b', parser.next().unwrap() );
+		assert_eq!( 4, parser.count_byte(b'a') );
+		assert_eq!( b'b', parser.next().unwrap() );
+		assert!( parser.next().is_none() );
+		assert_eq!( 0, parser.count_byte(b'a') );
+
+	}
+
+	#[test]
+	fn count_bytes() {
+
+		let mut parser = StrParser::new("a b\tc");
+		assert_eq!( 2, parser.count_bytes(b" \t") );
+		assert!( parser.next().is_none() );
+		assert_eq!( 0, parser.count_bytes(b" ") );
+
+		let mut parser = StrParser::new("a b\tc\t");
+		assert_eq!( [2, 1, 0], parser.scan_counts([b'\t', b' ', b'x']) );
+
+		// duplicates are only counted once
+		let mut parser = StrParser::new("aa");
+		assert_eq!( [2, 0], parser.scan_counts([b'a', b'a']) );
+		let mut parser = StrParser::new("aa");
+		assert_eq!( [0; 0], parser.scan_counts([]) );
+		assert!( parser.next().is_none() );
+
+	}
+
+	#[test]
+	fn advance_n() {
+
+		// IgnoreByte uses the default implementation
+		for n in 0..5 {
+			let mut parser = StrParser::new("abc");
+			let fast = (parser.advance_n(n), parser.offset());
+			let mut parser = StrParser::new("abc");
+			let mut ignore = parser.ignore_byte(0);
+			let slow = (ignore.advance_n(n), ignore.offset());
+			assert_eq!(fast, slow, "{}", n);
+		}
+
+		let mut parser = Parser::new(b"abc");
+		parser.next();
+		assert_eq!(parser.advance_n(5), Some(2));
+		assert!(parser.next().is_none());
+
+		let mut parser = TrackedStrParser::new("a\nb");
+		assert_eq!(parser.advance_n(3), Some(3));
+		assert_eq!((parser.line(), parser.col()), (2, 2));
+
+		let mut parser = StrParser::new("abc");
+		assert_eq!(parser.peek_len(2), Some(&b"ab"[..]));
+		assert!(parser.peek_len(4).is_none());
+		assert_eq!(parser.record().consume_len(4).unwrap_err(), 3);
+
+	}
+
+	#[test]
+	fn count_byte_fast_path() {
+
+		// IgnoreByte doesn't have the fast path
+		fn naive(s: &str, byte: u8) -> (usize, Option<u8>) {
+			let mut parser = StrParser::new(s);
+			let mut ignore = parser.ignore_byte(0);
+			let count = ignore.count_byte(byte);
+			(count, ignore.next())
+		}
+
+		fn fast(s: &str, byte: u8) -> (usize, Option<u8>) {
+			let mut parser = StrParser::new(s);
+			let count = parser.count_byte(byte);
+			(count, parser.next())
+		}
+
+		let long = " ".repeat(37) + "a";
+		let cases = ["", " ", "a", " a", "  a ", long.as_str(), &long[..20]];
+		for s in cases.iter() {
+			assert_eq!(fast(s, b' '), naive(s, b' '), "{:?}", s);
+		}
+
+		let mut parser = StrParser::new(&long);
+		parser.next();
+		assert_eq!(parser.consume_while_byte(b' ').next(), Some(b'a'));
+
+		let mut parser = StrParser::new(&long);
+		let n = parser.while_byte_fn(|&b| b == b' ').consume().offset();
+		assert_eq!(n, 37);
+
+	}
+
+	#[test]
+	fn count_while_byte_fn() {
+
+		let mut parser = StrParser::new("  \tab");
+		let is_ws = |b: &u8| b.is_ascii_whitespace();
+		assert_eq!( 3, parser.peek_count_while_byte_fn(is_ws) );
+		assert_eq!( 0, parser.offset() );
+		assert_eq!( 3, parser.count_while_byte_fn(is_ws) );
+		assert_eq!( 0, parser.peek_count_while_byte_fn(is_ws) );
+		assert_eq!( Some(b'a'), parser.next() );
+
+		let mut parser = Parser::new(b"aaa");
+		parser.next();
+		assert_eq!( 2, parser.peek_count_while_byte_fn(|&b| b == b'a') );
+		assert_eq!( 2, parser.count_while_byte_fn(|&b| b == b'a') );
+		assert!( parser.next().is_none() );
+
+	}
+
+	#[test]
+	fn consume_while_byte_fn_max() {
+
+		let is_a = |b: &u8| *b == b'a';
+
+		// within the bound
+		let mut parser = StrParser::new("aab");
+		assert!( parser.consume_while_byte_fn_max(is_a, 3).is_ok() );
+		assert_eq!( Some(b'b'), parser.next() );
+
+		// exactly the bound
+		let mut parser = StrParser::new("aab");
+		assert!( parser.consume_while_byte_fn_max(is_a, 2).is_ok() );
+		assert_eq!( 2, parser.offset() );
+
+		let mut parser = StrParser::new("aa");
+		assert!( parser.consume_while_byte_fn_max(is_a, 2).is_ok() );
+		assert!( parser.next().is_none() );
+
+		// over the bound
+		let mut parser = StrParser::new("aaab");
+		assert_eq!( 1, parser.consume_while_byte_fn_max(is_a, 1).unwrap_err() );
+		assert_eq!( 1, parser.offset() );
+		assert_eq!( 0, parser.consume_while_byte_fn_max(is_a, 0).unwrap_err() );
+
+		// in a segment
+		let mut parser = StrParser::new("aa,a");
+		let mut split = parser.split_on_byte(b',');
+		let seg = split.next().unwrap();
+		assert!( seg.consume_while_byte_fn_max(|_| true, 2).is_ok() );
+		assert!( seg.next().is_none() );
+
+	}
+
+	#[test]
+	fn advance_if_while() {
+
+		let mut parser = StrParser::new("aab");
+		assert!( parser.advance_if_while(|&b| b == b'a') );
+		assert!( !parser.advance_if_while(|&b| b == b'a') );
+		assert!( parser.advance_if_while(|&b| b == b'b') );
+		assert!( !parser.advance_if_while(|_| true) );
+		assert!( parser.next().is_none() );
+
+	}
+
+	#[test]
+	fn combining_multiple_iters() {
+
+		let s = b"ab\raaa\r aab\raa";
+
+		Parser::new(s)
+			.ignore_byte(b'\r')
+			.split_on_byte(b' ')
+			.for_each( |parser| {
+
+				// lets ignore b
+				// and count a
+				let count_a = parser
+					.ignore_byte(b'b')
+					.count_byte(b'a');
+
+				assert_eq!( count_a, 4 );
+
+			} );
+
+	}
+
+	#[test]
+	fn try_split_collect() {
+
+		let mut parser = StrParser::new("1,2,x,4");
+		let mut visited = 0;
+		let res: Result<Vec<u8>, _> = parser.try_split_collect(b',', |seg| {
+			visited += 1;
+			seg.record().consume_to_str().parse::<u8>()
+		});
+		assert!(res.is_err());
+		assert_eq!(visited, 3);
+
+		let mut parser = StrParser::new("1,2,3,4");
+		let res: Result<Vec<u8>, std::num::ParseIntError> = parser
+			.try_split_collect(b',', |seg| {
+				seg.record().consume_to_str().parse()
+			});
+		assert_eq!(res.unwrap(), [1, 2, 3, 4]);
+
+	}
+
+	#[test]
+	fn consume_while_byte_bounds() {
+
+		let s = b"\x00\x1f\x20\x7f\x80\xff";
+
+		let mut parser = Parser::new(s);
+		parser.consume_while_byte_le(0x1f);
+		assert_eq!(parser.next(), Some(0x20));
+
+		let mut parser = Parser::new(s);
+		parser.consume_while_byte_lt(0x1f);
+		assert_eq!(parser.next(), Some(0x1f));
+
+		let mut parser = Parser::new(s);
+		parser.consume_len(3).unwrap();
+		parser.consume_while_byte_ge(0x7f);
+		assert!(parser.next().is_none());
+
+		let mut parser = Parser::new(s);
+		parser.consume_len(3).unwrap();
+		parser.consume_while_byte_gt(0x7f);
+		assert_eq!(parser.next(), Some(0x7f));
+
+	}
+
+	#[test]
+	fn consume_until_any_byte() {
+
+		let mut parser = StrParser::new("a b\tc");
+		assert_eq!(parser.consume_until_any_byte(b" \t"), Some(b' '));
+		// doesn't consume the terminator
+		assert_eq!(parser.consume_until_any_byte(b" \t"), Some(b' '));
+		parser.next();
+		assert_eq!(parser.consume_until_any_byte(b" \t"), Some(b'\t'));
+		parser.next();
+		assert_eq!(parser.consume_until_any_byte(b" \t"), None);
+		assert!(parser.next().is_none());
+
+		let mut parser = StrParser::new("abc");
+		assert_eq!(parser.consume_until_any_byte(b""), None);
+		assert!(parser.next().is_none());
+
+	}
+
+	#[test]
+	fn consume_class() {
+
+		let class = byte_set::ByteSet::new().alpha().byte(b'-');
+
+		let mut parser = StrParser::new("content-type: text");
+		let s = parser
+			.record()
+			.consume_class(&class)
+			.to_str();
+		assert_eq!(s, "content-type");
+		assert_eq!(parser.next(), Some(b':'));
+
+	}
+
+	#[test]
+	fn advance_to_slice() {
+
+		let mut parser = StrParser::new("a */ b *");
+		let mut rec = parser.record();
+		assert!(rec.advance_to_slice(b"*/"));
+		assert_eq!(rec.to_str(), "a ");
+		assert_eq!(parser.next(), Some(b'*'));
+
+		// partial match at the end
+		let mut parser = StrParser::new("a * b *");
+		assert!(!parser.advance_to_slice(b"*/"));
+		assert!(parser.next().is_none());
+
+		// needle partially matches then fails
+		let mut parser = StrParser::new("aab aabc");
+		let mut rec = parser.record();
+		assert!(rec.advance_to_slice(b"aabc"));
+		assert_eq!(rec.to_str(), "aab ");
+
+		// needle at the start
+		let mut parser = StrParser::new("\r\nabc");
+		assert!(parser.advance_to_slice(b"\r\n"));
+		assert_eq!(parser.next(), Some(b'\r'));
+
+		// an empty needle matches immediately
+		assert!(parser.advance_to_slice(b""));
+		assert_eq!(parser.next(), Some(b'\n'));
+		parser.consume();
+		assert!(parser.advance_to_slice(b""));
+
+	}
+
+	#[test]
+	fn expect_byte() {
+
+		let s = b"abaa";
+
+		assert!( Parser::new(s)
+			.expect_byte(b'a').unwrap()
+			.expect_byte(b'a').is_err() );
+
+	}
+
+	#[test]
+	fn expect_one_of() {
+
+		let mut parser = Parser::new(b"+-a");
+		assert_eq!( parser.expect_one_of(b"+-"), Ok(b'+') );
+		assert_eq!( parser.expect_one_of(b"-"), Ok(b'-') );
+		assert_eq!( parser.expect_one_of(b""), Err(Some(b'a')) );
+		assert_eq!( parser.expect_one_of(b"+-"), Err(Some(b'a')) );
+		assert_eq!( parser.offset(), 2 );
+
+		// non ascii bytes
+		let mut parser = Parser::new(b"\xff");
+		assert_eq!( parser.expect_one_of(b"\x00\xff"), Ok(0xff) );
+		assert_eq!( parser.expect_one_of(b"\xff"), Err(None) );
+
+	}
+
+	#[test]
+	fn expect_err() {
+
+		let mut parser = StrParser::new("(a");
+		assert!(parser.expect_byte_err(b'(', "`(`").is_ok());
+
+		let err = parser.expect_byte_err(b'b', "b").unwrap_err();
+		assert_eq!((err.position(), err.found()), (1, Some(b'a')));
+		assert_eq!(parser.next(), Some(b'a'));
+
+		let err = parser.expect_slice_err(b")", "`)`").unwrap_err();
+		assert_eq!((err.position(), err.found()), (2, None));
+		assert_eq!(err.to_string(), "position 2: expected `)`, found end of input");
+
+	}
+
+	#[test]
+	fn expect_bytes_is_atomic() {
+
+		let mut parser = Parser::new(b"abcabd");
+
+		assert!(parser.expect_bytes(b"abc").is_ok());
+		let err = parser.expect_bytes(b"abc").unwrap_err();
+		assert_eq!(err.position(), 5);
+		assert_eq!(err.found(), Some(b'd'));
+		// the position should be restored to the start of the second abc
+		assert_eq!(parser.next(), Some(b'a'));
+
+		let mut parser = Parser::new(b"ab");
+		let err = parser.expect_bytes(b"abc").unwrap_err();
+		assert_eq!(err.position(), 2);
+		assert!(err.found().is_none());
+		assert_eq!(parser.next(), Some(b'a'));
+
+	}
+
+	#[test]
+	fn expect_slice() {
+
+		let mut parser = StrParser::new("truefalse");
+
+		// partial match restores the position
+		assert_eq!(parser.expect_slice(b"trap").unwrap_err(), 2);
+		assert_eq!(parser.expect_slice(b"false").unwrap_err(), 0);
+		assert!(parser.expect_slice(b"true").is_ok());
+
+		// to long
+		assert_eq!(parser.expect_slice(b"falsey").unwrap_err(), 5);
+		assert!(!parser.advance_if_slice(b"fals3"));
+		assert!(parser.advance_if_slice(b"false"));
+		assert!(parser.next().is_none());
+
+		let mut parser = StrParser::new("");
+		assert!(parser.advance_if_slice(b""));
+		assert_eq!(parser.expect_slice(b"a").unwrap_err(), 0);
+
+	}
+
+	#[test]
+	fn expect_slice_ignore_ascii_case() {
+
+		for input in ["Content-Length", "content-length", "CONTENT-LENGTH"].iter() {
+			let mut parser = StrParser::new(input);
+			let s = parser
+				.record()
+				.expect_slice_ignore_ascii_case(b"Content-Length").unwrap()
+				.to_str();
+			assert_eq!(&s, input);
+		}
+
+		let mut parser = StrParser::new("Content-Type");
+		assert_eq!(
+			parser.expect_slice_ignore_ascii_case(b"content-length").unwrap_err(),
+			8
+		);
+		assert!(!parser.advance_if_slice_ignore_ascii_case(b"CONTENT-LENGTH"));
+		assert!(parser.advance_if_slice_ignore_ascii_case(b"CONTENT-TYPE"));
+		assert!(parser.next().is_none());
+
+	}
+
+	#[test]
+	fn advance_if() {
+
+		let mut parser = Parser::new(b"ab");
+
+		assert!(parser.advance_if(|&b| b == b'a').unwrap());
+		assert!(!parser.advance_if(|&b| b == b'a').unwrap());
+		assert!(parser.advance_if(|&b| b == b'b').unwrap());
+		assert!(parser.advance_if(|&b| b == b'b').is_none());
+
+	}
+
+	#[test]
+	fn advance_if_any_byte() {
+
+		let mut parser = Parser::new(b"+a");
+
+		assert_eq!(parser.advance_if_any_byte(b"+-"), Some(b'+'));
+		assert!(parser.advance_if_any_byte(b"+-").is_none());
+		assert_eq!(parser.advance_if_any_byte(b"a"), Some(b'a'));
+		assert!(parser.advance_if_any_byte(b"a").is_none());
+
+	}
+
+	#[test]
+	fn next_if() {
+
+		let mut parser = Parser::new(b"ab");
+
+		assert_eq!(parser.next_if(|&b| b == b'a').unwrap(), b'a');
+		assert!(parser.next_if(|&b| b == b'x').is_none());
+		assert_eq!(parser.next_if(|&b| b == b'b').unwrap(), b'b');
+		assert!(parser.next_if(|&b| b == b'x').is_none());
+
+	}
+
+	#[test]
+	fn next_array() {
+
+		let mut parser = Parser::new(b"abcde");
+		assert_eq!(parser.peek_array(), Some(*b"abc"));
+		assert_eq!(parser.next_array(), Some(*b"ab"));
+		assert_eq!(parser.next_array::<0>(), Some([]));
+		assert!(parser.peek_array::<4>().is_none());
+		assert!(parser.next_array::<4>().is_none());
+		assert_eq!(parser.next_array(), Some(*b"cde"));
+		assert!(parser.next_array::<1>().is_none());
+
+	}
+
+	#[test]
+	fn next_int() {
+
+		let mut parser = Parser::new(&[
+			0x01, 0x02,
+			0x01, 0x02,
+			0xff, 0xff, 0xff, 0xfe,
+			1, 0, 0, 0, 0, 0, 0, 0,
+			0x7f
+		]);
+		assert_eq!(parser.next_u16_le(), Some(0x0201));
+		assert_eq!(parser.next_u16_be(), Some(0x0102));
+		assert_eq!(parser.next_i32_be(), Some(-2));
+		assert_eq!(parser.next_u64_le(), Some(1));
+
+		// not enough bytes
+		assert!(parser.next_i16_le().is_none());
+		assert_eq!(parser.next(), Some(0x7f));
+
+	}
+
+	#[test]
+	#[allow(deprecated)]
+	fn peek() {
+
+		let s = b"abaa";
+
+		assert_eq!( b'a', Parser::new(s).peek().unwrap() );
+		assert_eq!( b'a', Parser::new(s).peek_at(1).unwrap() );
+		assert_eq!( b'b', Parser::new(s).peek_at(2).unwrap() );
+		assert_eq!( b'a', Parser::new(s).peek_at(3).unwrap() );
+		assert!( Parser::new(s).peek_at(5).is_none() );
+
+	}
+
+	#[test]
+	fn prev_byte() {
+
+		let mut parser = Parser::new(b"ab");
+		assert!(parser.prev_byte().is_none());
+		parser.next();
+		assert_eq!(parser.prev_byte(), Some(b'a'));
+		parser.next();
+		assert_eq!(parser.prev_byte(), Some(b'b'));
+		parser.next();
+		assert_eq!(parser.prev_byte(), Some(b'b'));
+
+		let mut parser = StrParser::new("ab");
+		parser.next();
+		assert_eq!(parser.record().prev_byte(), Some(b'a'));
+		parser.reset();
+		assert!(parser.prev_byte().is_none());
+
+		// the split byte ended the segment
+		let mut parser = StrParser::new("ab,c");
+		let mut split = parser.split_on_byte(b',');
+		let ab = split.next().unwrap();
+		ab.consume();
+		assert_eq!(ab.prev_byte(), Some(b','));
+
+	}
+
+	#[test]
+	fn rewind_if() {
+
+		let mut parser = StrParser::new("a,");
+		assert!(!parser.rewind_if(|_| true));
+		parser.consume();
+		assert!(parser.rewind_if(|&b| b == b','));
+		assert!(!parser.rewind_if(|&b| b == b','));
+		assert_eq!(parser.offset(), 1);
+
+		// tests the last byte of an ended segment, not the split byte
+		let mut parser = StrParser::new("ab,c");
+		let mut split = parser.split_on_byte(b',');
+		let ab = split.next().unwrap();
+		ab.consume();
+		assert!(!ab.rewind_if(|&b| b == b','));
+		assert!(ab.rewind_if(|&b| b == b'b'));
+		assert_eq!(ab.record().consume_to_str(), "b");
+		assert_eq!(split.next().unwrap().record().consume_to_str(), "c");
+
+		// a split segment can't be rewound before its start
+		let mut parser = StrParser::new("a,b");
+		let mut split = parser.split_on_byte(b',');
+		split.next();
+		let b = split.next().unwrap();
+		assert!(!b.rewind_if(|&b| b == b','));
+		assert_eq!(b.record().consume_to_str(), "b");
+
+	}
+
+	#[test]
+	fn peek_nth() {
+
+		let s = b"abc";
+
+		let mut parser = Parser::new(s);
+		assert_eq!( parser.peek(), parser.peek_nth(0) );
+		assert_eq!( Some(b'c'), parser.peek_nth(2) );
+		assert!( parser.peek_nth(3).is_none() );
+		assert_eq!( 0, parser.offset() );
+
+		parser.consume();
+		assert!( parser.peek_nth(0).is_none() );
+
+		// segments end at the split byte
+		let mut parser = StrParser::new("ab,c");
+		let mut split = parser.split_on_byte(b',');
+		let ab = split.next().unwrap();
+		assert_eq!( Some(b'b'), ab.peek_nth(1) );
+		assert!( ab.peek_nth(2).is_none() );
+		assert_eq!( "ab", ab.record().consume_to_str() );
+
+	}
+
+	#[test]
+	fn peek_slice_eq_ci() {
+
+		let mut parser = Parser::new(b"Content-Length: 1");
+
+		assert!(parser.peek_slice_eq_ci(b"content-length"));
+		assert!(parser.peek_slice_eq_ci(b"CONTENT-LENGTH: 1"));
+		assert!(parser.peek_slice_eq_ci(b""));
+		assert!(!parser.peek_slice_eq_ci(b"content-type"));
+		// more bytes than remaining
+		assert!(!parser.peek_slice_eq_ci(b"content-length: 12"));
+		// the position never changes
+		assert_eq!(parser.next(), Some(b'C'));
+
+	}
+
+	#[test]
+	fn next_char() {
+
+		let s = "aü€😀";
+
+		let mut parser = StrParser::new(s);
+		let chars: Vec<_> = std::iter::from_fn(|| parser.next_char()).collect();
+		assert_eq!(chars, s.chars().collect::<Vec<_>>());
+
+		// next_char inside a split
+		let mut parser = StrParser::new("ü,😀");
+		let mut split = parser.split_on_byte(b',');
+		let first = split.next().unwrap();
+		assert_eq!(first.next_char(), Some('ü'));
+		assert!(first.next_char().is_none());
+		let second = split.next().unwrap();
+		assert_eq!(second.peek_char(), Some('😀'));
+		assert_eq!(second.record().consume_to_str(), "😀");
+
+		// invalid utf8 does not panic
+		let mut parser = Parser::new(b"\xffa");
+		assert!(parser.peek_char().is_none());
+		assert!(parser.next_char().is_none());
+		assert_eq!(parser.next(), Some(0xff));
+		assert_eq!(parser.next_char(), Some('a'));
+
+		// in the middle of a char
+		let mut parser = StrParser::new("ü");
+		parser.advance();
+		assert!(parser.next_char().is_none());
+		assert_eq!(parser.offset(), 1);
+
+	}
+
+	#[test]
+	fn try_next_char() {
+
+		// invalid continuation byte
+		let mut parser = Parser::new(b"\x80a");
+		assert!(parser.try_next_char().is_err());
+		assert_eq!(parser.next(), Some(0x80));
+		assert_eq!(parser.try_next_char().unwrap(), Some('a'));
+		assert_eq!(parser.try_next_char().unwrap(), None);
+
+		// truncated by the end of the input
+		let mut parser = Parser::new(&"😀".as_bytes()[..3]);
+		assert!(parser.try_next_char().is_err());
+		assert_eq!(parser.consume_and_count(), 3);
+
+	}
+
+	#[test]
+	fn starts_with() {
+
+		let mut parser = Parser::new(b"GET / HTTP/1.1");
+
+		assert!(parser.starts_with(b"GET"));
+		assert!(parser.starts_with(b""));
+		assert!(!parser.starts_with(b"get"));
+		assert!(parser.starts_with_ignore_ascii_case(b"get /"));
+		// partial matches
+		assert!(!parser.starts_with(b"GEX"));
+		assert!(!parser.starts_with_ignore_ascii_case(b"GET / HTTP/1.1 "));
+
+		assert_eq!(parser.next(), Some(b'G'));
+		parser.consume();
+		assert!(parser.starts_with(b""));
+		assert!(!parser.starts_with(b"1"));
+
+	}
+
+	#[test]
+	fn consume_while_ascii_whitespace() {
+
+		let mut parser = StrParser::new(" \t\r\n a \tb");
+
+		let ws = parser.record()
+			.consume_while_ascii_whitespace()
+			.to_str();
+		assert_eq!(ws, " \t\r\n ");
+		assert_eq!(parser.next(), Some(b'a'));
+
+		let b = parser
+			.skip_whitespace()
+			.record()
+			.consume_to_str();
+		assert_eq!(b, "b");
+
+		// nothing to skip
+		assert!(parser.skip_whitespace().next().is_none());
+
+	}
+
+	#[test]
+	fn take_while_byte_fn() {
+
+		let mut parser = StrParser::new("key = value");
+		let key = parser.take_while_byte_fn_str(|&b| b != b' ');
+		assert_eq!(key, "key");
+
+		parser.skip_whitespace();
+		assert_eq!(parser.take_while_byte_fn(|&b| b == b'='), b"=");
+		parser.skip_whitespace();
+
+		let mut split = parser.split_on_byte(b'l');
+		let va = split.next().unwrap()
+			.take_while_byte_fn_str(|_| true);
+		assert_eq!(va, "va");
+		assert_eq!(split.next().unwrap().take_while_byte_fn_str(|_| true), "ue");
+
+	}
+
+	#[test]
+	#[should_panic]
+	fn take_while_byte_fn_str_invalid_utf8() {
+
+		Parser::new(b"\xff").take_while_byte_fn_str(|_| true);
+
 	}
 
-	/// Returns a slice from the start of recording until now.
-	///
-	/// ## Panics
-	/// If not called in context of a recorder. Meaning before
-	/// calling `record`.
-	#[inline]
-	fn to_slice(&self) -> &'s [u8] {
-		let start = self.recorder().expect("no recorder found").pos() + 1;
-		let end = self.pit().record_pos() + 1;
+	#[test]
+	fn take_until_byte() {
+
+		let mut parser = StrParser::new("a;b;c");
+		assert_eq!(parser.take_until_byte(b';'), b"a");
+		assert_eq!(parser.take_until_byte(b';'), b"");
+		assert_eq!(parser.take_through_byte_str(b';'), ";");
+		assert_eq!(parser.take_through_byte_str(b';'), "b;");
+		assert_eq!(parser.take_until_byte_str(b';'), "c");
+		assert!(parser.is_empty());
 
-		&self.slice()[start..end]
 	}
 
-	/// Returns a `str` from the start of recording until the current position
-	/// without checking if the data is valid utf8.
-	/// ## Panics
-	/// Panics if not called after `record` gets called.
-	/// ## Safety
-	/// This function is safe if `Self::is_valid_utf8` returns `true`.
-	#[inline]
-	unsafe fn to_str_unchecked(&self) -> &'s str {
-		std::str::from_utf8_unchecked(self.to_slice())
+	#[test]
+	fn take_until_byte_absent() {
+
+		let mut parser = StrParser::new("abc");
+		assert_eq!(parser.take_until_byte_str(b';'), "abc");
+		assert!(parser.is_empty());
+
+		let mut parser = StrParser::new("abc");
+		assert_eq!(parser.take_through_byte(b';'), b"abc");
+		assert!(parser.is_empty());
+		assert_eq!(parser.take_through_byte(b';'), b"");
+
 	}
 
-	/// ## Safety
-	/// Returning `false` is always safe. 
-	/// If you return `true` the entire underlying slice must be valid utf8.
-	unsafe fn is_valid_utf8() -> bool;
+	#[cfg(feature = "alloc")]
+	#[test]
+	fn consume_to_owned() {
+
+		let (ab, cd) = {
+			// the owned values outlive the input
+			let input = String::from("ab,cd");
+			let mut parser = StrParser::new(&input);
+			let mut split = parser.split_on_byte(b',');
+			let ab = split.next().unwrap().record().consume_to_vec();
+			let cd = split.next().unwrap().record().consume_to_string();
+			(ab, cd)
+		};
+		assert_eq!(ab, b"ab");
+		assert_eq!(cd, "cd");
 
-	/// Returns a `str` from the start of recording until the current position.
-	///
-	/// ## Example
-	/// ```
-	/// # use byte_parser::{Parser, StrParser, ParseIterator};
-	/// let str_from_slice = Parser::new(b"abc")
-	///		.record()
-	/// 	.consume()
-	/// 	.to_str();
-	/// assert_eq!(str_from_slice, "abc");
-	///
-	/// let str_from_str = StrParser::new("abc")
-	/// 	.record()
-	/// 	.consume()
-	/// 	.to_str();
-	/// assert_eq!(str_from_str, "abc");
-	/// ```
-	///
-	/// ## Panics
-	/// Panics if not called after `record` was called. 
-	/// Or if invalid utf8 is present.
-	#[inline]
-	fn to_str(&self) -> &'s str {
-		if unsafe { Self::is_valid_utf8() } {
-			// Safe because is_valid_utf8 guaranties everything is valid utf8
-			unsafe { self.to_str_unchecked() }
-		} else {
-			std::str::from_utf8(self.to_slice()).expect("invalid utf8")
-		}
 	}
 
-	/// Returns a `str` from the start of recording until the current position.
-	///
-	/// ## Example
-	/// ```
-	/// # use byte_parser::{Parser, StrParser, ParseIterator};
-	/// let str_from_slice = Parser::new(b"abc")
-	///		.record()
-	/// 	.consume()
-	/// 	.try_to_str().expect("slice is not valid utf8");
-	/// assert_eq!(str_from_slice, "abc");
-	///
-	/// let str_from_str = StrParser::new("abc")
-	/// 	.record()
-	/// 	.consume()
-	/// 		// can never return Err
-	/// 	.try_to_str().unwrap();
-	/// assert_eq!(str_from_str, "abc");
-	/// ```
-	///
-	/// ## Panics
-	/// Panics if not called after `record` was called.
-	#[inline]
-	fn try_to_str(&self) -> Result<&'s str, std::str::Utf8Error> {
-		if unsafe { Self::is_valid_utf8() } {
-			// Safe because is_valid_utf8 guaranties everything is valid utf8
-			Ok(unsafe { self.to_str_unchecked() })
-		} else {
-			std::str::from_utf8(self.to_slice())
-		}
+	#[cfg(feature = "alloc")]
+	#[test]
+	fn consume_map_bytes() {
+
+		let mut buf = vec![];
+		let mut parser = StrParser::new("a-b,c-d");
+		let mut split = parser.split_on_byte(b',');
+
+		split.next().unwrap()
+			.consume_map_bytes(&mut buf, |b| if b == b'-' { None } else { Some(b) });
+		assert_eq!(buf, b"ab");
+
+		// the buffer is appended to
+		split.next().unwrap()
+			.consume_map_bytes(&mut buf, |b| Some(b.to_ascii_uppercase()));
+		assert_eq!(buf, b"abC-D");
+		assert!(split.next().is_none());
+
 	}
 
-	/// Consumes the iterator and then returns a slice from the start of recording
-	/// until the current position.
-	///
-	/// ## Panics
-	/// Panics if not called after `record` was called.
-	#[inline]
-	fn consume_to_slice(&mut self) -> &'s [u8] {
-		self.consume().to_slice()
+	#[cfg(feature = "alloc")]
+	#[test]
+	fn to_str_lossy() {
+
+		use std::borrow::Cow;
+
+		let mut parser = Parser::new(b"a\xffb,cd");
+		let parts: Vec<_> = parser.split_on_byte(b',')
+			.map_and_collect(|p| p.record().consume_to_str_lossy());
+		assert_eq!(parts, ["a\u{FFFD}b", "cd"]);
+		assert!(matches!(parts[0], Cow::Owned(_)));
+		assert!(matches!(parts[1], Cow::Borrowed(_)));
+
+		let mut parser = StrParser::new("äb");
+		let s = parser.record().consume_to_str_lossy();
+		assert!(matches!(s, Cow::Borrowed("äb")));
+
 	}
 
-	/// Consumes the iterator and then returns a str from the start of recording
-	/// until the current position. Without checking if the underlying data
-	/// is valid utf8.
-	///
-	/// ## Panics
-	/// Panics if not called after `record` was called.
-	#[inline]
-	unsafe fn consume_to_str_unchecked(&mut self) -> &'s str {
-		self.consume().to_str_unchecked()
+	#[test]
+	fn slice_between() {
+
+		let mut parser = StrParser::new("a,bc,d");
+		let mut pits = vec![parser.pit()];
+		while parser.next().is_some() {
+			if parser.peek() == Some(b',') {
+				pits.push(parser.pit());
+			}
+		}
+		assert_eq!(parser.slice_between(pits[0], pits[1]), b"a");
+		assert_eq!(parser.slice_between(pits[1], pits[2]), b",bc");
+		assert_eq!(parser.slice_between(pits[1], pits[1]), b"");
+
+		// inside a segment the split byte is not returned
+		let mut parser = StrParser::new("ab,c");
+		let mut split = parser.split_on_byte(b',');
+		let ab = split.next().unwrap();
+		let start = ab.pit();
+		ab.consume();
+		assert_eq!(ab.slice_between(start, ab.pit()), b"ab");
+
 	}
 
-	/// Consumes the iterator and then returns a str from the start of recording
-	/// until the current position.
-	///
-	/// ## Panics
-	/// Panics if not called after `record` was called or if the data contains invalid
-	/// utf8.
-	#[inline]
-	fn consume_to_str(&mut self) -> &'s str {
-		self.consume().to_str()
+	#[test]
+	#[should_panic]
+	fn slice_between_reversed() {
+		let mut parser = StrParser::new("ab");
+		let start = parser.pit();
+		parser.next();
+		let end = parser.pit();
+		parser.slice_between(end, start);
 	}
 
-	/// Consumes the iterator and then returns a str from the start of recording
-	/// until the current position if the data is valid utf8.
-	///
-	/// ## Panics
-	/// Panics if not called after `record` was called.
-	#[inline]
-	fn consume_try_to_str(&mut self) -> Result<&'s str, std::str::Utf8Error> {
-		self.consume().try_to_str()
+	#[test]
+	fn skip_until_slice() {
+
+		let mut parser = StrParser::new("a-b--c--");
+		assert_eq!(parser.skip_until_slice(b"--").unwrap().offset(), 3);
+		// already at the needle
+		assert_eq!(parser.skip_until_slice(b"--").unwrap().offset(), 3);
+		assert_eq!(parser.skip_past_slice(b"--").unwrap().next(), Some(b'c'));
+		assert_eq!(parser.skip_past_slice(b"--").unwrap().offset(), 8);
+		assert!(parser.skip_until_slice(b"--").is_none());
+
+		let mut parser = StrParser::new("a-b-");
+		assert!(parser.skip_past_slice(b"--").is_none());
+		assert!(parser.next().is_none());
+
+		// stays inside the segment
+		let mut parser = StrParser::new("ab,cd");
+		let mut split = parser.split_on_byte(b',');
+		assert!(split.next().unwrap().skip_until_slice(b"cd").is_none());
+		let cd = split.next().unwrap();
+		assert_eq!(cd.skip_until_slice(b"d").unwrap().next(), Some(b'd'));
+
+		// an empty needle matches immediately
+		let mut parser = StrParser::new("ab");
+		assert_eq!(parser.skip_until_slice(b"").unwrap().offset(), 0);
+		assert_eq!(parser.skip_past_slice(b"").unwrap().offset(), 0);
+
 	}
 
-	/// Returns ```&mut Self``` if the function returns `true` on the next byte.
-	/// Else returns the byte that was received.
-	#[inline]
-	fn expect_byte_fn<F>(&mut self, f: F) -> Result<&mut Self, Option<u8>>
-	where F: Fn(u8) -> bool {
-		self.next()
-			.expect_byte_fn(f)
-			.map(|_| self)
+	#[test]
+	fn peek2_and_peek3() {
+
+		let mut parser = StrParser::new(">>=");
+		assert_eq!(parser.peek2(), Some((b'>', b'>')));
+		assert_eq!(parser.peek3(), Some((b'>', b'>', b'=')));
+		assert_eq!(parser.offset(), 0);
+
+		parser.next();
+		assert_eq!(parser.peek2(), Some((b'>', b'=')));
+		assert!(parser.peek3().is_none());
+		assert_eq!(parser.offset(), 1);
+
+		parser.next();
+		assert!(parser.peek2().is_none());
+		assert_eq!(parser.next(), Some(b'='));
+		assert!(parser.peek2().is_none());
+
+		// segments end at the split byte
+		let mut parser = StrParser::new("ab,c");
+		let mut split = parser.split_on_byte(b',');
+		let ab = split.next().unwrap();
+		assert!(ab.peek3().is_none());
+		assert_eq!(ab.peek2(), Some((b'a', b'b')));
+		assert_eq!(ab.record().consume_to_str(), "ab");
+
 	}
 
-	/// Returns ```&mut Self``` if the function byte is equal to the next byte.
-	/// Else returns the actual byte that was received.
-	#[inline]
-	fn expect_byte(&mut self, byte: u8) -> Result<&mut Self, Option<u8>> {
-		self.expect_byte_fn(|b| b == byte)
+	#[test]
+	fn peek_while_byte_fn() {
+
+		let mut parser = StrParser::new("12,ab");
+		let pit = parser.pit();
+		assert_eq!(parser.peek_while_byte_fn(u8::is_ascii_digit), b"12");
+		assert_eq!(parser.pit(), pit);
+		assert_eq!(parser.peek_while_byte_fn(u8::is_ascii_alphabetic), b"");
+		assert_eq!(parser.pit(), pit);
+
+		let mut split = parser.split_on_byte(b',');
+		let num = split.next().unwrap();
+		// stops at the end of the segment
+		assert_eq!(num.peek_while_byte_fn(|_| true), b"12");
+		assert_eq!(num.record().consume_to_str(), "12");
+
+		let ab = split.next().unwrap();
+		assert_eq!(ab.peek_while_byte_fn(|_| true), b"ab");
+		assert_eq!(ab.next(), Some(b'a'));
+
 	}
 
-	/// Returns ```&mut Self``` if the end was reached (next returns None).
-	#[inline]
-	fn expect_none(&mut self) -> Result<&mut Self, u8> {
-		match self.next() {
-			Some(b) => Err(b),
-			None => Ok(self)
-		}
+	#[test]
+	fn try_consume_len() {
+
+		let mut parser = StrParser::new("abc");
+		parser.next();
+		let pit = parser.pit();
+		assert!(parser.try_consume_len(3).is_none());
+		assert_eq!(parser.pit(), pit);
+
+		let mut split = parser.split_on_byte(b'c');
+		let b = split.next().unwrap();
+		assert!(b.try_consume_len(2).is_none());
+		assert!(b.try_consume_len(1).unwrap().next().is_none());
+		assert!(split.next().is_some());
+
 	}
 
-	/// Returns a `ParseIterator` that always returns None.
-	///
-	/// ## Example
-	/// ```
-	/// # use byte_parser::{StrParser, ParseIterator};
-	/// let mut s = StrParser::new("abc");
-	/// assert_eq!(b'a', s.next().unwrap());
-	/// let mut s = s.stop();
-	/// assert!(s.next().is_none());
-	/// ```
-	#[inline]
-	fn stop(&mut self) -> Stop<'_, Self>
-	where Self: Sized {
-		Stop::new(self)
+	#[test]
+	fn attempt() {
+
+		let mut parser = StrParser::new("12ab");
+		let pit = parser.pit();
+
+		// partially consumes then fails
+		let r = parser.attempt(|p| {
+			p.consume_while_byte_fn(u8::is_ascii_digit);
+			p.expect_byte(b'x').ok().map(|_| ())
+		});
+		assert!(r.is_none());
+		assert_eq!(parser.pit(), pit);
+
+		let num = parser.attempt(|p| {
+			let s = p.take_while_byte_fn_str(u8::is_ascii_digit);
+			s.parse::<u32>().ok()
+		});
+		assert_eq!(num, Some(12));
+		assert_eq!(parser.next(), Some(b'a'));
+
 	}
 
-}
+	#[test]
+	fn rewind() {
 
-#[cfg(test)]
-mod tests {
+		let mut parser = StrParser::new("abc");
+		assert_eq!(parser.rewind(1).unwrap_err(), 0);
+		assert!(parser.rewind(0).is_ok());
 
-	use crate::*;
+		parser.consume();
+		parser.rewind(3).unwrap();
+		assert_eq!(parser.remaining_str(), "abc");
+
+		// can't rewind before the recording started
+		parser.next();
+		let mut rec = parser.record();
+		rec.consume();
+		assert_eq!(rec.rewind(3).unwrap_err(), 2);
+		rec.rewind(1).unwrap();
+		assert_eq!(rec.to_str(), "b");
+		assert_eq!(rec.consume_to_str(), "bc");
+
+	}
 
 	#[test]
-	fn test_count_byte() {
+	fn contains_byte() {
 
-		let s = b"baaaab";
+		let mut parser = Parser::new(b"abcdefghijklmnopqrstuvwxyz");
+		assert!(parser.contains_byte(b'z'));
+		parser.consume_len(3).unwrap();
+		assert!(!parser.contains_byte(b'c'));
+		assert!(parser.contains_byte(b'd'));
+		assert_eq!(parser.offset(), 3);
 
-		let mut parser = Parser::new( s );
-		assert_eq!( 0, parser.count_byte(b'a') );
-		assert_eq!( b'b', parser.next().unwrap() );
-		assert_eq!( 4, parser.count_byte(b'a') );
-		assert_eq!( b'b', parser.next().unwrap() );
-		assert!( parser.next().is_none() );
-		assert_eq!( 0, parser.count_byte(b'a') );
+		// through a recorder
+		assert!(parser.record().contains_byte(b'q'));
+
+		parser.consume();
+		assert!(!parser.contains_byte(b'z'));
 
 	}
 
 	#[test]
-	fn combining_multiple_iters() {
+	fn try_to_slice() {
+
+		let mut parser = Parser::new(b"a\xff");
+		assert!( parser.try_to_slice().is_none() );
+		assert!( parser.try_to_str_opt().is_none() );
+
+		let mut rec = parser.record();
+		assert_eq!( rec.try_to_str_opt(), Some("") );
+		rec.next();
+		assert_eq!( rec.try_to_str_opt(), Some("a") );
+		rec.next();
+		assert_eq!( rec.try_to_slice(), Some(&b"a\xff"[..]) );
+		// invalid utf8
+		assert!( rec.try_to_str_opt().is_none() );
+
+		// the split has no recorder
+		let mut parser = StrParser::new("a,b");
+		let mut split = parser.split_on_byte(b',');
+		let seg = split.next().unwrap();
+		assert!( seg.try_to_slice().is_none() );
+		assert_eq!( seg.record().consume().try_to_str_opt(), Some("a") );
 
-		let s = b"ab\raaa\r aab\raa";
+	}
 
-		Parser::new(s)
-			.ignore_byte(b'\r')
-			.split_on_byte(b' ')
-			.for_each( |parser| {
+	#[test]
+	fn scoped() {
 
-				// lets ignore b
-				// and count a
-				let count_a = parser
-					.ignore_byte(b'b')
-					.count_byte(b'a');
+		let mut parser = StrParser::new("abcdef");
 
-				assert_eq!( count_a, 4 );
+		// consumes the limit even if f stops early
+		let a = parser.scoped(3, |p| p.next());
+		assert_eq!(a, Some(b'a'));
+		assert_eq!(parser.offset(), 3);
 
-			} );
+		// f can't read past the limit
+		let s = parser.scoped(2, |p| p.record().consume_to_str());
+		assert_eq!(s, "de");
+
+		// a limit after the end consumes everything
+		let n = parser.scoped(5, |p| p.consume_and_count());
+		assert_eq!(n, 1);
+		assert!(parser.next().is_none());
 
 	}
 
 	#[test]
-	fn expect_byte() {
+	fn split_into() {
 
-		let s = b"abaa";
+		let mut out = [&b""[..]; 3];
 
-		assert!( Parser::new(s)
-			.expect_byte(b'a').unwrap()
-			.expect_byte(b'a').is_err() );
+		let mut parser = StrParser::new("a,b");
+		assert_eq!(parser.split_into(b',', &mut out), 2);
+		assert_eq!(out[..2], [b"a", b"b"]);
+
+		let mut parser = StrParser::new("a,,b,c");
+		assert_eq!(parser.split_into(b',', &mut out), 3);
+		assert_eq!(out, [&b"a"[..], b"", b"b"]);
+		assert_eq!(parser.record().consume_to_str(), "c");
+
+		let mut parser = StrParser::new("a,,b,c");
+		assert_eq!(parser.splitn_into(b',', &mut out), 3);
+		assert_eq!(out, [&b"a"[..], b"", b"b,c"]);
+
+		let mut parser = StrParser::new("");
+		assert_eq!(parser.split_into(b',', &mut out), 0);
+		assert_eq!(parser.splitn_into(b',', &mut out), 0);
+
+		let mut parser = StrParser::new("a");
+		assert_eq!(parser.split_into(b',', &mut []), 0);
+		assert_eq!(parser.splitn_into(b',', &mut []), 0);
+		assert_eq!(parser.next(), Some(b'a'));
 
 	}
 
 	#[test]
-	fn advance_if() {
+	fn remaining() {
 
-		let mut parser = Parser::new(b"ab");
+		let mut parser = StrParser::new("abc");
+		assert_eq!(parser.remaining(), b"abc");
+		assert_eq!(parser.remaining_str(), "abc");
 
-		assert!(parser.advance_if(|&b| b == b'a').unwrap());
-		assert!(!parser.advance_if(|&b| b == b'a').unwrap());
-		assert!(parser.advance_if(|&b| b == b'b').unwrap());
-		assert!(parser.advance_if(|&b| b == b'b').is_none());
+		parser.consume_len(2).unwrap();
+		assert_eq!(parser.remaining_str(), "c");
+
+		// peeking does not change the remaining
+		assert_eq!(parser.peek(), Some(b'c'));
+		assert_eq!(parser.remaining_str(), "c");
+
+		parser.consume();
+		assert_eq!(parser.remaining_str(), "");
+
+		let parser = Parser::new(b"");
+		assert_eq!(parser.remaining(), b"");
 
 	}
 
 	#[test]
-	fn next_if() {
+	fn len_and_is_empty() {
 
 		let mut parser = Parser::new(b"ab");
+		assert_eq!(parser.len(), 2);
+		assert!(!parser.is_empty());
+
+		parser.next();
+		assert_eq!(parser.len(), 1);
+
+		parser.next();
+		assert_eq!(parser.len(), 0);
+		assert!(parser.is_empty());
+
+		let mut parser = Parser::new(b"");
+		assert_eq!(parser.len(), 0);
+		assert!(parser.is_empty());
+
+		// respects the bounds of a segment
+		let mut parser = StrParser::new("abc,de");
+		let mut split = parser.split_on_byte(b',');
+		let abc = split.next().unwrap();
+		let mut rec = abc.record();
+		rec.next();
+		assert_eq!(rec.len(), 2);
+		// does not advance
+		assert_eq!(rec.to_str(), "a");
+		rec.consume();
+		assert_eq!(rec.len(), 0);
+		assert_eq!(split.next().unwrap().len(), 2);
 
-		assert_eq!(parser.next_if(|&b| b == b'a').unwrap(), b'a');
-		assert!(parser.next_if(|&b| b == b'x').is_none());
-		assert_eq!(parser.next_if(|&b| b == b'b').unwrap(), b'b');
-		assert!(parser.next_if(|&b| b == b'x').is_none());
+	}
+
+	#[test]
+	fn try_remaining_str() {
+
+		let mut parser = Parser::new(b"a\xffb");
+		assert!(parser.try_remaining_str().is_err());
+		parser.consume_len(2).unwrap();
+		assert_eq!(parser.try_remaining_str(), Ok("b"));
+		parser.consume();
+		assert_eq!(parser.try_remaining_str(), Ok(""));
+
+		let mut parser = StrParser::new("äb");
+		parser.consume_len(2).unwrap();
+		assert_eq!(parser.try_remaining_str(), Ok("b"));
 
 	}
 
 	#[test]
-	fn peek() {
+	#[should_panic]
+	fn remaining_str_invalid_utf8() {
 
-		let s = b"abaa";
+		Parser::new(b"a\xff").remaining_str();
 
-		assert_eq!( b'a', Parser::new(s).peek().unwrap() );
-		assert_eq!( b'a', Parser::new(s).peek_at(1).unwrap() );
-		assert_eq!( b'b', Parser::new(s).peek_at(2).unwrap() );
-		assert_eq!( b'a', Parser::new(s).peek_at(3).unwrap() );
-		assert!( Parser::new(s).peek_at(5).is_none() );
+	}
+
+	#[test]
+	fn consume_with() {
+
+		let mut bytes = vec![];
+		let mut parser = StrParser::new("a\r\nb");
+		parser.ignore_byte(b'\r').consume_with(|b| bytes.push(b));
+		assert_eq!(bytes, b"a\nb");
+		assert!(parser.next().is_none());
+
+		let mut bytes = vec![];
+		let mut parser = StrParser::new("a,b,c");
+		parser.ignore_byte(b',')
+			.consume_len_with(2, |b| bytes.push(b))
+			.unwrap();
+		assert_eq!(bytes, b"ab");
+		assert_eq!(parser.next(), Some(b','));
+
+		let mut parser = StrParser::new("ab");
+		assert_eq!(parser.consume_len_with(3, |_| {}).unwrap_err(), 2);
 
 	}
 
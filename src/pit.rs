@@ -1,7 +1,7 @@
 
 use crate::position::Position;
 
-use std::fmt::Debug;
+use core::fmt::Debug;
 
 pub trait PointInTime: Debug + Copy + Eq {
 
@@ -29,6 +29,16 @@ impl ParserPointInTime {
 			pos: Position::null()
 		}
 	}
+
+	/// Creates a point in time where the next byte is at `offset`.
+	pub(crate) fn at(offset: usize) -> Self {
+		Self {
+			pos: match offset {
+				0 => Position::null(),
+				o => (o - 1).into()
+			}
+		}
+	}
 }
 
 impl PointInTime for ParserPointInTime {
@@ -39,4 +49,46 @@ impl PointInTime for ParserPointInTime {
 	unsafe fn set_pos(&mut self, pos: Position) {
 		self.pos = pos;
 	}
-}
\ No newline at end of file
+}
+
+/// PointInTime which keeps track of the line and column.
+/// Used by TrackedStrParser
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrackedPointInTime {
+	pub(crate) pos: Position,
+	pub(crate) line: usize,
+	pub(crate) col: usize
+}
+
+impl TrackedPointInTime {
+	pub(crate) fn new() -> Self {
+		Self {
+			pos: Position::null(),
+			line: 1,
+			col: 1
+		}
+	}
+
+	/// Returns the line of the next byte, starting at 1.
+	pub fn line(&self) -> usize {
+		self.line
+	}
+
+	/// Returns the column of the next byte, starting at 1.
+	/// The column is counted in bytes.
+	pub fn col(&self) -> usize {
+		self.col
+	}
+}
+
+impl PointInTime for TrackedPointInTime {
+	fn pos(&self) -> Position {
+		self.pos
+	}
+
+	/// ## Warning
+	/// This does not update the line and column.
+	unsafe fn set_pos(&mut self, pos: Position) {
+		self.pos = pos;
+	}
+}
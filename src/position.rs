@@ -1,5 +1,5 @@
 
-use std::ops::{ Deref, Add };
+use core::ops::{ Deref, Add, Sub };
 
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -13,6 +13,28 @@ impl Position {
 	pub fn opt(&self) -> Option<usize> {
 		self.0
 	}
+
+	/// Like `Add` but returns `None` instead of panicking.
+	///
+	/// Adding zero on null returns `None` since there is no offset before
+	/// the start.
+	pub fn checked_add(self, n: usize) -> Option<usize> {
+		match self.0 {
+			Some(o) => o.checked_add(n),
+			None => n.checked_sub(1)
+		}
+	}
+
+	/// Like `Sub` but returns `None` instead of panicking.
+	pub fn checked_sub(self, n: usize) -> Option<Self> {
+		match (self.0, n) {
+			(_, 0) => Some(self),
+			(Some(o), n) if n <= o => Some(Self(Some(o - n))),
+			// going back to before the start
+			(Some(o), n) if n == o + 1 => Some(Self::null()),
+			_ => None
+		}
+	}
 }
 
 impl Deref for Position {
@@ -34,8 +56,99 @@ impl Add<usize> for Position {
 	}
 }
 
+impl Sub<usize> for Position {
+	type Output = Position;
+
+	/// Moves the position back by `other` bytes. Subtracting one from
+	/// position zero returns null.
+	///
+	/// panics if the result would be before null
+	fn sub(self, other: usize) -> Position {
+		self.checked_sub(other)
+			.expect("position subtraction underflowed")
+	}
+}
+
 impl From<usize> for Position {
 	fn from(n: usize) -> Self {
 		Self(Some(n))
 	}
 }
+
+/// A range of byte offsets, `start` is inclusive and `end` exclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Span {
+	pub start: usize,
+	pub end: usize
+}
+
+impl Span {
+	pub fn new(start: usize, end: usize) -> Self {
+		Self {start, end}
+	}
+
+	pub fn len(&self) -> usize {
+		self.end - self.start
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.start == self.end
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+
+	use super::*;
+
+	#[test]
+	fn add() {
+
+		assert_eq!(Position::null() + 1, 0);
+		assert_eq!(Position::from(2) + 1, 3);
+		assert_eq!(Position::from(2) + 0, 2);
+
+		assert_eq!(Position::null().checked_add(0), None);
+		assert_eq!(Position::null().checked_add(3), Some(2));
+		assert_eq!(Position::from(1).checked_add(0), Some(1));
+		assert_eq!(Position::from(usize::MAX).checked_add(1), None);
+
+	}
+
+	#[test]
+	#[should_panic]
+	fn add_zero_on_null() {
+
+		let _ = Position::null() + 0;
+
+	}
+
+	#[test]
+	fn sub() {
+
+		assert_eq!(Position::from(3) - 2, Position::from(1));
+		assert_eq!(Position::from(3) - 3, Position::from(0));
+		assert_eq!(Position::from(3) - 4, Position::null());
+		assert_eq!(Position::null() - 0, Position::null());
+
+		assert_eq!(Position::from(3).checked_sub(5), None);
+		assert_eq!(Position::null().checked_sub(1), None);
+
+		// sub is the inverse of add
+		for n in 0..5 {
+			let pos = Position::from(Position::null() + 5);
+			assert_eq!(Position::from(pos - n + n), pos);
+		}
+
+	}
+
+	#[test]
+	#[should_panic]
+	fn sub_before_null() {
+
+		let _ = Position::from(0) - 2;
+
+	}
+
+}
@@ -0,0 +1,80 @@
+
+use crate::ParseIterator;
+
+/// `Iterator` returned by `positions_where`.
+///
+/// Yields the offset in the underlying slice of every byte for which the
+/// function returns `true`.
+#[derive(Debug)]
+pub struct Positions<'a, T, F> {
+	inner: &'a mut T,
+	f: F
+}
+
+impl<'s, 'a, T, F> Positions<'a, T, F>
+where
+	T: ParseIterator<'s>,
+	F: Fn(&u8) -> bool {
+	pub(super) fn new(inner: &'a mut T, f: F) -> Self {
+		Self {inner, f}
+	}
+}
+
+impl<'s, 'a, T, F> Iterator for Positions<'a, T, F>
+where
+	T: ParseIterator<'s>,
+	F: Fn(&u8) -> bool {
+
+	type Item = usize;
+
+	fn next(&mut self) -> Option<usize> {
+		loop {
+			let b = self.inner.next()?;
+			if (self.f)(&b) {
+				// the byte is at the current position
+				return Some(self.inner.offset() - 1)
+			}
+		}
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		(0, Some(self.inner.remaining().len()))
+	}
+
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+	use crate::*;
+
+	#[test]
+	fn positions() {
+
+		let mut parser = StrParser::new("a,b,,c");
+		let pos: Vec<_> = parser.positions_where(|&b| b == b',').collect();
+		assert_eq!(pos, [1, 3, 4]);
+		assert!(parser.next().is_none());
+
+		// offsets are absolute
+		let mut parser = StrParser::new("ab;c;d,e;f");
+		parser.consume_len(2).unwrap();
+		let mut split = parser.split_on_byte(b',');
+		let pos: Vec<_> = split.next().unwrap()
+			.positions_where(|&b| b == b';')
+			.collect();
+		assert_eq!(pos, [2, 4]);
+		let pos: Vec<_> = split.next().unwrap()
+			.positions_where(|&b| b == b';')
+			.collect();
+		assert_eq!(pos, [8]);
+
+		let mut parser = StrParser::new("");
+		assert!(parser.positions_where(|_| true).next().is_none());
+
+	}
+
+}
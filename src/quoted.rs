@@ -0,0 +1,419 @@
+//!
+//! Parse quoted strings with escapes.
+//!
+//! `parse_quoted` only removes the escape byte, `parse_escaped_string`
+//! decodes C and JSON like escapes (`\n`, `\xNN`, `\uNNNN`, ...).
+//!
+//! ## Example
+//! ```
+//! # use byte_parser::{StrParser, ParseIterator, quoted::parse_quoted};
+//! # use std::borrow::Cow;
+//! let mut parser = StrParser::new(r#""plain", "with \"escapes\"""#);
+//! let mut split = parser.split_on_byte(b',');
+//!
+//! let plain = parse_quoted(split.next().unwrap(), b'"', b'\\').unwrap();
+//! assert!(matches!(plain, Cow::Borrowed("plain")));
+//!
+//! let field = split.next().unwrap();
+//! field.consume_while_byte(b' ');
+//! let escaped = parse_quoted(field, b'"', b'\\').unwrap();
+//! assert_eq!(escaped, r#"with "escapes""#);
+//! ```
+
+use crate::{ParseIterator, numbers::parse_hex};
+
+use core::fmt;
+use alloc::{borrow::Cow, string::String, vec::Vec};
+
+
+/// Parses a string enclosed in `quote`. The byte after an `escape` is
+/// always taken literally, so `escape` followed by `quote` or `escape`
+/// results in just the second byte.
+///
+/// Consumes the opening and closing quote. Returns a borrowed str if
+/// the string contains no escapes and an owned one otherwise.
+///
+/// Returns `None` without advancing if the next byte is not `quote`,
+/// if the string is not terminated or if it is not valid utf8.
+pub fn parse_quoted<'s, I>(
+	iter: &mut I,
+	quote: u8,
+	escape: u8
+) -> Option<Cow<'s, str>>
+where I: ParseIterator<'s> {
+	let pit = iter.pit();
+	let s = parse_quoted_inner(iter, quote, escape);
+	if s.is_none() {
+		iter.restore_pit(pit);
+	}
+	s
+}
+
+fn parse_quoted_inner<'s, I>(
+	iter: &mut I,
+	quote: u8,
+	escape: u8
+) -> Option<Cow<'s, str>>
+where I: ParseIterator<'s> {
+	iter.next_if(|&b| b == quote)?;
+
+	let slice = iter.slice();
+	// only set if an escape was found
+	let mut owned: Option<Vec<u8>> = None;
+	// start of the part which was not yet copied into owned
+	let mut start = iter.offset();
+
+	loop {
+		let b = iter.next()?;
+		if b == quote {
+			break
+		}
+
+		if b == escape {
+			let end = iter.offset() - 1;
+			owned.get_or_insert_with(Vec::new)
+				.extend_from_slice(&slice[start..end]);
+			iter.next()?;
+			// the escaped byte is part of the next copy
+			start = iter.offset() - 1;
+		}
+	}
+
+	let end = iter.offset() - 1;
+	// splitting on ascii bytes can't break valid utf8
+	let valid = unsafe { I::is_valid_utf8() }
+		&& quote.is_ascii() && escape.is_ascii();
+
+	match owned {
+		None => {
+			let s = &slice[start..end];
+			let s = if valid {
+				// Safe because is_valid_utf8 guaranties everything is valid utf8
+				unsafe { core::str::from_utf8_unchecked(s) }
+			} else {
+				core::str::from_utf8(s).ok()?
+			};
+			Some(Cow::Borrowed(s))
+		},
+		Some(mut v) => {
+			v.extend_from_slice(&slice[start..end]);
+			let s = if valid {
+				// Safe because only ascii bytes were removed
+				unsafe { String::from_utf8_unchecked(v) }
+			} else {
+				String::from_utf8(v).ok()?
+			};
+			Some(Cow::Owned(s))
+		}
+	}
+}
+
+
+/// What went wrong in `parse_escaped_string`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EscapeErrorKind {
+	/// The string does not start with the quote.
+	NotQuoted,
+	/// The end was reached before the closing quote.
+	Unterminated,
+	/// An unknown escape, contains the byte after the backslash.
+	InvalidEscape(u8),
+	/// `\x` or `\u` is not followed by enough hex digits.
+	InvalidHex,
+	/// A `\u` surrogate without its other half.
+	LoneSurrogate,
+	/// The decoded string is not valid utf8. The position is the byte or
+	/// `\xNN` escape which starts the invalid sequence.
+	InvalidUtf8
+}
+
+/// Error returned by `parse_escaped_string`.
+///
+/// ## Example
+/// ```
+/// # use byte_parser::{StrParser, quoted::{parse_escaped_string, EscapeErrorKind}};
+/// let mut parser = StrParser::new(r#""a\qb""#);
+/// let err = parse_escaped_string(&mut parser, b'"').unwrap_err();
+/// assert_eq!(err.kind(), EscapeErrorKind::InvalidEscape(b'q'));
+/// assert_eq!(err.position(), 2);
+/// assert_eq!(err.to_string(), "position 2: invalid escape '\\q'");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EscapeError {
+	position: usize,
+	kind: EscapeErrorKind
+}
+
+impl EscapeError {
+
+	fn new(position: usize, kind: EscapeErrorKind) -> Self {
+		Self {position, kind}
+	}
+
+	/// Returns the byte offset at which the error occured. For escapes
+	/// this is the offset of the backslash.
+	pub fn position(&self) -> usize {
+		self.position
+	}
+
+	/// Returns what went wrong.
+	pub fn kind(&self) -> EscapeErrorKind {
+		self.kind
+	}
+
+}
+
+impl fmt::Display for EscapeError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "position {}: ", self.position)?;
+
+		match self.kind {
+			EscapeErrorKind::NotQuoted => f.write_str("expected quote"),
+			EscapeErrorKind::Unterminated => f.write_str("unterminated string"),
+			EscapeErrorKind::InvalidEscape(b) if b.is_ascii_graphic() => {
+				write!(f, "invalid escape '\\{}'", b as char)
+			},
+			EscapeErrorKind::InvalidEscape(b) => {
+				write!(f, "invalid escape 0x{:02x}", b)
+			},
+			EscapeErrorKind::InvalidHex => f.write_str("invalid hex escape"),
+			EscapeErrorKind::LoneSurrogate => f.write_str("lone surrogate"),
+			EscapeErrorKind::InvalidUtf8 => f.write_str("invalid utf8")
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for EscapeError {}
+
+/// Parses a string enclosed in `quote` and decodes the escapes `\n`, `\t`,
+/// `\r`, `\0`, `\b`, `\f`, `\\`, `\/`, `\'`, `\"`, `\xNN` (a
+/// single byte) and `\uNNNN` (including utf16 surrogate pairs).
+///
+/// Consumes the opening and closing quote. On error nothing is consumed.
+///
+/// ## Example
+/// ```
+/// # use byte_parser::{StrParser, ParseIterator, quoted::parse_escaped_string};
+/// let mut parser = StrParser::new(r#""tab\there \u00e4 \ud83d\ude00\x21" rest"#);
+/// let s = parse_escaped_string(&mut parser, b'"').unwrap();
+/// assert_eq!(s, "tab\there ä 😀!");
+/// assert_eq!(parser.next(), Some(b' '));
+/// ```
+pub fn parse_escaped_string<'s, I>(
+	iter: &mut I,
+	quote: u8
+) -> Result<String, EscapeError>
+where I: ParseIterator<'s> {
+	let pit = iter.pit();
+	let s = parse_escaped_inner(iter, quote);
+	if s.is_err() {
+		iter.restore_pit(pit);
+	}
+	s
+}
+
+fn parse_escaped_inner<'s, I>(
+	iter: &mut I,
+	quote: u8
+) -> Result<String, EscapeError>
+where I: ParseIterator<'s> {
+	use EscapeErrorKind::*;
+
+	let start = iter.offset();
+	iter.next_if(|&b| b == quote)
+		.ok_or_else(|| EscapeError::new(start, NotQuoted))?;
+
+	let mut v = Vec::new();
+	// the source offsets of non ascii bytes which were not produced by
+	// a char escape, only those can start invalid utf8
+	let mut raw = Vec::new();
+
+	loop {
+		let unterminated = |iter: &I| EscapeError::new(iter.offset(), Unterminated);
+
+		let b = iter.next().ok_or_else(|| unterminated(iter))?;
+		if b == quote {
+			break
+		} else if b != b'\\' {
+			if !b.is_ascii() {
+				raw.push((v.len(), iter.offset() - 1));
+			}
+			v.push(b);
+			continue
+		}
+
+		let pos = iter.offset() - 1;
+		let err = |kind| EscapeError::new(pos, kind);
+
+		let e = iter.next().ok_or_else(|| unterminated(iter))?;
+		let c = match e {
+			b'n' => '\n',
+			b't' => '\t',
+			b'r' => '\r',
+			b'0' => '\0',
+			b'b' => '\x08',
+			b'f' => '\x0c',
+			b'\\' | b'/' | b'\'' | b'"' => e as char,
+			b'x' => {
+				let n = parse_hex::<_, 2>(iter).ok_or_else(|| err(InvalidHex))? as u8;
+				if !n.is_ascii() {
+					raw.push((v.len(), pos));
+				}
+				v.push(n);
+				continue
+			},
+			b'u' => {
+				let n = parse_hex::<_, 4>(iter).ok_or_else(|| err(InvalidHex))?;
+				let n = match n {
+					0xD800..=0xDBFF => {
+						let low = iter.expect_bytes(b"\\u").ok()
+							.and_then(|iter| parse_hex::<_, 4>(iter))
+							.filter(|low| (0xDC00..=0xDFFF).contains(low))
+							.ok_or_else(|| err(LoneSurrogate))?;
+						0x10000 + ((n - 0xD800) << 10) + (low - 0xDC00)
+					},
+					0xDC00..=0xDFFF => return Err(err(LoneSurrogate)),
+					n => n
+				};
+				// can't fail since surrogates are handled
+				core::char::from_u32(n).ok_or_else(|| err(LoneSurrogate))?
+			},
+			e if e == quote => e as char,
+			e => return Err(err(InvalidEscape(e)))
+		};
+
+		let mut buf = [0; 4];
+		v.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+	}
+
+	String::from_utf8(v).map_err(|e| {
+		let invalid = e.utf8_error().valid_up_to();
+		let position = raw.iter()
+			.find(|(i, _)| *i == invalid)
+			.map_or(start, |(_, pos)| *pos);
+		EscapeError::new(position, InvalidUtf8)
+	})
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+	use super::*;
+	use crate::*;
+
+	#[test]
+	fn borrowed_and_owned() {
+
+		let mut parser = StrParser::new(r#""abc"d"#);
+		let s = parse_quoted(&mut parser, b'"', b'\\').unwrap();
+		assert!(matches!(s, Cow::Borrowed("abc")));
+		assert_eq!(parser.next(), Some(b'd'));
+
+		let mut parser = StrParser::new(r#""a\"b\\c\d""#);
+		let s = parse_quoted(&mut parser, b'"', b'\\').unwrap();
+		assert!(matches!(&s, Cow::Owned(_)));
+		assert_eq!(s, r#"a"b\cd"#);
+		assert!(parser.next().is_none());
+
+		let mut parser = Parser::new(b"'it/'s'");
+		let s = parse_quoted(&mut parser, b'\'', b'/').unwrap();
+		assert_eq!(s, "it's");
+
+		let mut parser = StrParser::new(r#""""#);
+		let s = parse_quoted(&mut parser, b'"', b'\\').unwrap();
+		assert_eq!(s, "");
+
+	}
+
+	#[test]
+	fn escaped() {
+
+		let cases: [(&str, &str); 8] = [
+			(r#""""#, ""),
+			(r#""a\nb""#, "a\nb"),
+			(r#""\t\r\0\b\f""#, "\t\r\0\x08\x0c"),
+			(r#""\\\/\'\"""#, "\\/'\""),
+			(r#""\x41\x7e""#, "A~"),
+			(r#""\xc3\xa4""#, "ä"),
+			(r#""\u00E4\u20ac""#, "ä€"),
+			(r#""\uD83D\uDE00ä""#, "😀ä")
+		];
+
+		for (input, expected) in cases.iter() {
+			let mut parser = StrParser::new(input);
+			let s = parse_escaped_string(&mut parser, b'"').unwrap();
+			assert_eq!(s, *expected, "{}", input);
+			assert!(parser.next().is_none());
+		}
+
+		// escaped custom quote
+		let mut parser = StrParser::new(r"'it\'s'");
+		assert_eq!(parse_escaped_string(&mut parser, b'\'').unwrap(), "it's");
+
+	}
+
+	#[test]
+	fn escaped_errors() {
+
+		use EscapeErrorKind::*;
+
+		let cases: [(&str, usize, EscapeErrorKind); 13] = [
+			("abc", 0, NotQuoted),
+			(r#""abc"#, 4, Unterminated),
+			(r#""a\"#, 3, Unterminated),
+			(r#""a\q""#, 2, InvalidEscape(b'q')),
+			(r#""\x4""#, 1, InvalidHex),
+			(r#""\u12g4""#, 1, InvalidHex),
+			(r#""\uD83D""#, 1, LoneSurrogate),
+			(r#""\uD83Dx""#, 1, LoneSurrogate),
+			(r#""\uD83D\u0041""#, 1, LoneSurrogate),
+			(r#""a\uDE00""#, 2, LoneSurrogate),
+			(r#""\xff""#, 1, InvalidUtf8),
+			// the lead byte is valid until the next byte
+			(r#""ab\xc3\xc3""#, 3, InvalidUtf8),
+			(r#""\xc3\xa9\xff""#, 9, InvalidUtf8)
+		];
+
+		for (input, position, kind) in cases.iter() {
+			let mut parser = StrParser::new(input);
+			let err = parse_escaped_string(&mut parser, b'"').unwrap_err();
+			assert_eq!((err.position(), err.kind()), (*position, *kind), "{}", input);
+			// nothing was consumed
+			assert_eq!(parser.offset(), 0);
+		}
+
+		// invalid utf8 in the input itself
+		let mut parser = Parser::new(b"\"a\xff\"");
+		let err = parse_escaped_string(&mut parser, b'"').unwrap_err();
+		assert_eq!((err.position(), err.kind()), (2, InvalidUtf8));
+
+		let err = EscapeError::new(3, InvalidEscape(0x01));
+		assert_eq!(err.to_string(), "position 3: invalid escape 0x01");
+
+	}
+
+	#[test]
+	fn invalid() {
+
+		let cases: [&[u8]; 5] = [
+			b"abc",
+			b"\"abc",
+			b"\"abc\\\"",
+			b"\"a\xffb\"",
+			b""
+		];
+
+		for case in cases.iter() {
+			let mut parser = Parser::new(case);
+			assert!(parse_quoted(&mut parser, b'"', b'\\').is_none());
+			// nothing was consumed
+			assert_eq!(parser.offset(), 0);
+		}
+
+	}
+
+}
@@ -0,0 +1,492 @@
+//!
+//! Parse data from a `std::io::Read` without loading everything into memory.
+//!
+//! `ReadParser` does not implement `ParseIterator` since the trait returns
+//! slices which live as long as the input, while `ReadParser` reuses its
+//! buffer. Instead it provides a forward only subset of the api:
+//! - `peek`, `next`, `consume_while_byte_fn` and `consume` to advance.
+//! - `record` which keeps the recorded bytes in the buffer, growing it if
+//!   they don't fit, until the returned `ReadRecorder` is dropped.
+//! - `split_on_byte` which returns every segment as a `Parser`, so the
+//!   full `ParseIterator` api is available within a segment.
+//!
+//! Rewinding, restoring a point in time and the other adapters are not
+//! supported on the `ReadParser` itself.
+//!
+//! ## Example
+//! ```
+//! # use byte_parser::{ParseIterator, read_parser::ReadParser};
+//! let log = "INFO start\nWARN disk\nINFO stop\n".as_bytes();
+//! let mut parser = ReadParser::new(log);
+//!
+//! let mut warnings = 0;
+//! let mut lines = parser.split_on_byte(b'\n');
+//! while let Some(mut line) = lines.next().unwrap() {
+//! 	if line.advance_if_slice(b"WARN") {
+//! 		warnings += 1;
+//! 	}
+//! }
+//! assert_eq!(warnings, 1);
+//! ```
+
+use crate::{Parser, byte_search::find_byte};
+
+use std::io::{self, Read};
+use std::ops::{Deref, DerefMut};
+use std::str::Utf8Error;
+
+
+const DEFAULT_CAPACITY: usize = 8 * 1024;
+
+/// Forward only parser over a `Read` with an internal buffer.
+///
+/// The buffer grows if a segment does not fit into it.
+#[derive(Debug)]
+pub struct ReadParser<R> {
+	reader: R,
+	buf: Vec<u8>,
+	// the bytes between start and end were read but not consumed
+	start: usize,
+	end: usize,
+	// how many bytes were consumed
+	offset: usize,
+	// the offset of the first byte which needs to stay in the buffer
+	// because it is recorded
+	keep: Option<usize>,
+	eof: bool,
+	// if the last consumed byte ended a segment
+	after_delimiter: bool
+}
+
+impl<R: Read> ReadParser<R> {
+
+	/// Creates a new `ReadParser` with a buffer of 8 KiB.
+	pub fn new(reader: R) -> Self {
+		Self::with_capacity(DEFAULT_CAPACITY, reader)
+	}
+
+	/// Creates a new `ReadParser` with an initial buffer of `capacity` bytes.
+	pub fn with_capacity(capacity: usize, reader: R) -> Self {
+		Self {
+			reader,
+			buf: vec![0; capacity.max(1)],
+			start: 0,
+			end: 0,
+			offset: 0,
+			keep: None,
+			eof: false,
+			after_delimiter: false
+		}
+	}
+
+	/// Returns how many bytes were consumed.
+	pub fn offset(&self) -> usize {
+		self.offset
+	}
+
+	/// Reads more data into the buffer, moving the unconsumed bytes to the
+	/// front or growing the buffer if needed. Returns how many bytes were
+	/// read, zero means the end was reached.
+	fn fill(&mut self) -> io::Result<usize> {
+		if self.end == self.buf.len() {
+			// recorded bytes can't be dropped
+			let keep = match self.keep {
+				Some(keep) => self.start - (self.offset - keep),
+				None => self.start
+			};
+
+			if keep > 0 {
+				self.buf.copy_within(keep..self.end, 0);
+				self.end -= keep;
+				self.start -= keep;
+			} else {
+				let len = self.buf.len();
+				self.buf.resize(len * 2, 0);
+			}
+		}
+
+		loop {
+			match self.reader.read(&mut self.buf[self.end..]) {
+				Ok(n) => {
+					self.end += n;
+					self.eof = n == 0;
+					return Ok(n)
+				},
+				Err(e) if e.kind() == io::ErrorKind::Interrupted => {},
+				Err(e) => return Err(e)
+			}
+		}
+	}
+
+	/// Returns the next byte without consuming it.
+	pub fn peek(&mut self) -> io::Result<Option<u8>> {
+		if self.start == self.end && !self.eof {
+			self.fill()?;
+		}
+
+		Ok(self.buf[self.start..self.end].first().copied())
+	}
+
+	/// Returns the next byte.
+	// not an Iterator since reading can fail
+	#[allow(clippy::should_implement_trait)]
+	pub fn next(&mut self) -> io::Result<Option<u8>> {
+		let b = self.peek()?;
+		if b.is_some() {
+			self.consume_buffered(1);
+		}
+		Ok(b)
+	}
+
+	fn consume_buffered(&mut self, len: usize) {
+		if len > 0 {
+			self.start += len;
+			self.offset += len;
+			self.after_delimiter = false;
+		}
+	}
+
+	/// Consumes bytes while `f` returns `true`.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::read_parser::ReadParser;
+	/// let mut parser = ReadParser::new("   value".as_bytes());
+	/// parser.consume_while_byte_fn(u8::is_ascii_whitespace).unwrap();
+	/// assert_eq!(parser.next().unwrap(), Some(b'v'));
+	/// ```
+	pub fn consume_while_byte_fn<F>(&mut self, f: F) -> io::Result<&mut Self>
+	where F: Fn(&u8) -> bool {
+		loop {
+			let rest = &self.buf[self.start..self.end];
+			let len = rest.iter()
+				.position(|b| !f(b))
+				.unwrap_or(rest.len());
+			self.consume_buffered(len);
+
+			if self.start < self.end || self.eof || self.fill()? == 0 {
+				return Ok(self)
+			}
+		}
+	}
+
+	/// Consumes until the end of the reader.
+	pub fn consume(&mut self) -> io::Result<&mut Self> {
+		self.consume_while_byte_fn(|_| true)
+	}
+
+	/// Starts recording, every byte consumed from the returned
+	/// `ReadRecorder` stays in the buffer until it is dropped.
+	///
+	/// The buffer grows if the recorded bytes don't fit, so recording
+	/// everything loads the full reader into memory.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::read_parser::ReadParser;
+	/// let mut parser = ReadParser::new("key=value".as_bytes());
+	/// let mut key = parser.record();
+	/// key.consume_while_byte_fn(|&b| b != b'=').unwrap();
+	/// assert_eq!(key.to_slice(), b"key");
+	/// drop(key);
+	///
+	/// parser.next().unwrap();
+	/// assert_eq!(parser.record().consume_to_slice().unwrap(), b"value");
+	/// ```
+	pub fn record(&mut self) -> ReadRecorder<'_, R> {
+		ReadRecorder::new(self)
+	}
+
+	/// Splits the reader at `byte`, returning every segment as a `Parser`.
+	///
+	/// See `next_segment`.
+	pub fn split_on_byte(&mut self, byte: u8) -> ReadSplitOnByte<'_, R> {
+		ReadSplitOnByte {
+			parser: self,
+			byte
+		}
+	}
+
+	/// Returns the bytes until the next `byte` and consumes them together
+	/// with `byte`.
+	///
+	/// Like `split_on_byte`, if the data ends with `byte` an empty last
+	/// segment is returned and empty data returns no segment.
+	pub fn next_segment(&mut self, byte: u8) -> io::Result<Option<&[u8]>> {
+		// how many bytes were already searched
+		let mut searched = 0;
+		let (seg_end, consumed) = loop {
+			let rest = &self.buf[self.start + searched..self.end];
+			if let Some(i) = find_byte(byte, rest) {
+				let seg_end = self.start + searched + i;
+				break (seg_end, seg_end + 1 - self.start)
+			}
+			searched = self.end - self.start;
+
+			if self.eof || self.fill()? == 0 {
+				// only a delimiter is followed by an empty segment
+				if self.start == self.end && !self.after_delimiter {
+					return Ok(None)
+				}
+				break (self.end, self.end - self.start)
+			}
+		};
+
+		let start = self.start;
+		self.start += consumed;
+		self.offset += consumed;
+		self.after_delimiter = seg_end < self.start;
+
+		Ok(Some(&self.buf[start..seg_end]))
+	}
+
+}
+
+/// Records the bytes consumed from a `ReadParser`.
+///
+/// Derefs to the `ReadParser`, so every method of it can be used to
+/// advance. Recording stops when it is dropped.
+#[derive(Debug)]
+pub struct ReadRecorder<'a, R> {
+	parser: &'a mut ReadParser<R>,
+	// the offset where the recording started
+	start: usize,
+	prev_keep: Option<usize>
+}
+
+impl<'a, R> ReadRecorder<'a, R> {
+
+	fn new(parser: &'a mut ReadParser<R>) -> Self {
+		let start = parser.offset;
+		let prev_keep = parser.keep;
+		// an outer recorder started before this one
+		parser.keep = Some(prev_keep.unwrap_or(start));
+
+		Self {
+			parser,
+			start,
+			prev_keep
+		}
+	}
+
+	/// Returns the bytes from the start of recording until now.
+	pub fn to_slice(&self) -> &[u8] {
+		let len = self.parser.offset - self.start;
+		let end = self.parser.start;
+		&self.parser.buf[end - len..end]
+	}
+
+	/// Returns the bytes from the start of recording until now as a `str`.
+	pub fn try_to_str(&self) -> Result<&str, Utf8Error> {
+		std::str::from_utf8(self.to_slice())
+	}
+
+}
+
+impl<R: Read> ReadRecorder<'_, R> {
+
+	/// Consumes until the end of the reader and then returns the bytes
+	/// from the start of recording.
+	pub fn consume_to_slice(&mut self) -> io::Result<&[u8]> {
+		self.parser.consume()?;
+		Ok(self.to_slice())
+	}
+
+}
+
+impl<R> Deref for ReadRecorder<'_, R> {
+	type Target = ReadParser<R>;
+
+	fn deref(&self) -> &ReadParser<R> {
+		self.parser
+	}
+}
+
+impl<R> DerefMut for ReadRecorder<'_, R> {
+	fn deref_mut(&mut self) -> &mut ReadParser<R> {
+		self.parser
+	}
+}
+
+impl<R> Drop for ReadRecorder<'_, R> {
+	fn drop(&mut self) {
+		self.parser.keep = self.prev_keep;
+	}
+}
+
+/// Splits a `ReadParser` at a byte.
+///
+/// Created by `ReadParser::split_on_byte`.
+#[derive(Debug)]
+pub struct ReadSplitOnByte<'a, R> {
+	parser: &'a mut ReadParser<R>,
+	byte: u8
+}
+
+impl<R: Read> ReadSplitOnByte<'_, R> {
+
+	/// Returns the next segment as a `Parser` over the buffer.
+	// can't implement Iterator since the segment borrows self
+	#[allow(clippy::should_implement_trait)]
+	pub fn next(&mut self) -> io::Result<Option<Parser<'_>>> {
+		let seg = self.parser.next_segment(self.byte)?;
+		Ok(seg.map(Parser::new))
+	}
+
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+	use super::*;
+	use crate::ParseIterator;
+
+	// returns at most one byte per read
+	struct Slow<'a>(&'a [u8]);
+
+	impl Read for Slow<'_> {
+		fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+			match (self.0.split_first(), buf.first_mut()) {
+				(Some((&b, rest)), Some(first)) => {
+					*first = b;
+					self.0 = rest;
+					Ok(1)
+				},
+				_ => Ok(0)
+			}
+		}
+	}
+
+	fn segments<R: Read>(mut parser: ReadParser<R>) -> Vec<Vec<u8>> {
+		let mut v = vec![];
+		while let Some(seg) = parser.next_segment(b',').unwrap() {
+			v.push(seg.to_vec());
+		}
+		v
+	}
+
+	#[test]
+	fn segments_like_split_on_byte() {
+
+		let cases: [(&str, &[&str]); 5] = [
+			("a,bc,d", &["a", "bc", "d"]),
+			("a,", &["a", ""]),
+			(",", &["", ""]),
+			("a", &["a"]),
+			("", &[])
+		];
+
+		for (input, expected) in cases.iter() {
+			let expected: Vec<_> = expected.iter()
+				.map(|s| s.as_bytes().to_vec())
+				.collect();
+
+			let parser = ReadParser::new(input.as_bytes());
+			assert_eq!(segments(parser), expected, "{}", input);
+
+			// small buffer which needs to grow
+			let parser = ReadParser::with_capacity(1, Slow(input.as_bytes()));
+			assert_eq!(segments(parser), expected, "{}", input);
+		}
+
+	}
+
+	#[test]
+	fn grows_and_moves_the_buffer() {
+
+		let input = "aaaaaaaaaa,bb,cccccccccccccccccccc,d";
+		let mut parser = ReadParser::with_capacity(4, Slow(input.as_bytes()));
+		let expected: Vec<_> = input.split(',').collect();
+
+		for seg in expected {
+			assert_eq!(parser.next_segment(b',').unwrap().unwrap(), seg.as_bytes());
+		}
+		assert!(parser.next_segment(b',').unwrap().is_none());
+		assert_eq!(parser.offset(), input.len());
+
+	}
+
+	#[test]
+	fn bytes() {
+
+		let mut parser = ReadParser::with_capacity(2, Slow(b"ab,c"));
+		assert_eq!(parser.peek().unwrap(), Some(b'a'));
+		assert_eq!(parser.next().unwrap(), Some(b'a'));
+		assert_eq!(parser.next_segment(b',').unwrap().unwrap(), b"b");
+		assert_eq!(parser.next().unwrap(), Some(b'c'));
+		assert!(parser.next().unwrap().is_none());
+		assert!(parser.peek().unwrap().is_none());
+		assert_eq!(parser.offset(), 4);
+		assert!(parser.next_segment(b',').unwrap().is_none());
+
+	}
+
+	#[test]
+	fn record_keeps_the_bytes() {
+
+		let input = "aaaaaaaaaa,bb,cccccccccccccccccccc,d";
+		for &cap in [1, 4, 64].iter() {
+			let mut parser = ReadParser::with_capacity(cap, Slow(input.as_bytes()));
+
+			let mut rec = parser.record();
+			rec.consume_while_byte_fn(|&b| b != b',').unwrap();
+			assert_eq!(rec.to_slice(), b"aaaaaaaaaa");
+			drop(rec);
+
+			parser.next().unwrap();
+			let mut outer = parser.record();
+			outer.next_segment(b',').unwrap();
+			let mut inner = outer.record();
+			inner.next_segment(b',').unwrap();
+			assert_eq!(inner.try_to_str(), Ok("cccccccccccccccccccc,"));
+			drop(inner);
+			assert_eq!(outer.to_slice(), b"bb,cccccccccccccccccccc,");
+			assert_eq!(outer.consume_to_slice().unwrap(), &input.as_bytes()[11..]);
+			drop(outer);
+
+			assert_eq!(parser.offset(), input.len());
+			assert_eq!(parser.record().consume_to_slice().unwrap(), b"");
+		}
+
+	}
+
+	#[test]
+	fn consume_while_byte_fn() {
+
+		let mut parser = ReadParser::with_capacity(1, Slow(b"   a b"));
+		parser.consume_while_byte_fn(|&b| b == b' ').unwrap();
+		assert_eq!(parser.offset(), 3);
+		// nothing to consume
+		parser.consume_while_byte_fn(|&b| b == b' ').unwrap();
+		assert_eq!(parser.next().unwrap(), Some(b'a'));
+		parser.consume().unwrap();
+		assert_eq!(parser.offset(), 6);
+		assert!(parser.next().unwrap().is_none());
+
+	}
+
+	#[test]
+	fn split_on_byte() {
+
+		let mut parser = ReadParser::with_capacity(2, Slow(b"k=v,key=value,"));
+		let mut split = parser.split_on_byte(b',');
+		let mut pairs = vec![];
+		while let Some(mut seg) = split.next().unwrap() {
+			let key = seg.record()
+				.consume_while_byte_fn(|&b| b != b'=')
+				.to_slice();
+			seg.next();
+			pairs.push((key.to_vec(), seg.remaining().to_vec()));
+		}
+
+		assert_eq!(pairs, [
+			(b"k".to_vec(), b"v".to_vec()),
+			(b"key".to_vec(), b"value".to_vec()),
+			(vec![], vec![])
+		]);
+
+	}
+
+}
@@ -7,19 +7,32 @@ use crate::{
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Recorder {
-	pos: Position
+	pos: Position,
+	// the record position when trim_end_while was called
+	// and how many bytes are excluded at the end
+	trim_end: Option<(Position, usize)>
 }
 
 impl Recorder {
 
 	pub(super) fn new(pos: Position) -> Self {
-		Self {pos}
+		Self {pos, trim_end: None}
 	}
 
 	pub fn pos(&self) -> Position {
 		self.pos
 	}
 
+	/// Returns the end of the recording in the slice. The trim of
+	/// `trim_end_while` only applies until the position changes.
+	pub(crate) fn end(&self, record_pos: Position) -> usize {
+		let end = record_pos + 1;
+		match self.trim_end {
+			Some((pos, len)) if pos == record_pos => end - len,
+			_ => end
+		}
+	}
+
 }
 
 
@@ -38,6 +51,78 @@ where T: ParseIterator<'s> {
 			recorder: Recorder::new(pos)
 		}
 	}
+
+	/// Returns how many bytes were consumed since the recording started.
+	///
+	/// Same as `recorded_len` which can't panic here since this is always
+	/// a recorder.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("abc");
+	/// let mut rec = parser.record();
+	/// rec.consume_len(2).unwrap();
+	/// assert_eq!(rec.consumed(), 2);
+	/// ```
+	#[inline]
+	pub fn consumed(&self) -> usize {
+		self.recorded_len()
+	}
+
+	/// Moves the start of the recording forward past all leading recorded
+	/// bytes for which `f` returns `true`, excluding them from `to_slice`.
+	///
+	/// Only affects bytes which were already consumed.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new(" \tvalue \t;");
+	/// let value = parser
+	/// 	.record()
+	/// 	.consume_while_byte_fn(|&b| b != b';')
+	/// 	.trim_start_while(|&b| b == b' ' || b == b'\t')
+	/// 	.trim_end_while(|&b| b == b' ' || b == b'\t')
+	/// 	.to_str();
+	/// assert_eq!(value, "value");
+	/// assert_eq!(parser.next(), Some(b';'));
+	/// ```
+	pub fn trim_start_while<F>(&mut self, f: F) -> &mut Self
+	where F: Fn(&u8) -> bool {
+		let len = self.to_slice().iter()
+			.take_while(|b| f(b))
+			.count();
+
+		if len > 0 {
+			let start = self.recorder.pos() + 1;
+			self.recorder.pos = (start + len - 1).into();
+		}
+
+		self
+	}
+
+	/// Excludes all trailing recorded bytes for which `f` returns `true`
+	/// from `to_slice`.
+	///
+	/// The position does not change, the excluded bytes stay consumed.
+	/// Consuming or rewinding afterwards discards the trim.
+	pub fn trim_end_while<F>(&mut self, f: F) -> &mut Self
+	where F: Fn(&u8) -> bool {
+		let len = self.to_slice().iter()
+			.rev()
+			.take_while(|b| f(b))
+			.count();
+
+		let pos = self.inner.pit().record_pos();
+		// trims done before are already excluded from to_slice
+		let prev = match self.recorder.trim_end {
+			Some((trim_pos, prev)) if trim_pos == pos => prev,
+			_ => 0
+		};
+		self.recorder.trim_end = Some((pos, prev + len));
+		self
+	}
 }
 
 
@@ -62,19 +147,51 @@ where T: ParseIterator<'s> {
 		self.inner.advance()
 	}
 
+	fn advance_n(&mut self, n: usize) -> Option<usize> {
+		self.inner.advance_n(n)
+	}
+
 	fn recorder(&self) -> Option<&Recorder> {
 		Some(&self.recorder)
 	}
 
+	fn recorder_depth(&self) -> usize {
+		self.inner.recorder_depth() + 1
+	}
+
+	/// Does not allow to rewind before the start of the recording.
+	fn rewind(&mut self, len: usize) -> Result<&mut Self, usize> {
+		// the bytes excluded by trim_end_while are still consumed
+		let start = self.recorder.pos() + 1;
+		let max = (self.inner.pit().record_pos() + 1) - start;
+		if len > max {
+			return Err(max)
+		}
+
+		self.inner.rewind(len)?;
+		Ok(self)
+	}
+
 	// fn to_str(&self) -> Self::ToStrResult {
 	// 	self.inner.to_str()
 	// }
 
+	// advance is not changed so the inner consume can be used
+	fn consume(&mut self) -> &mut Self {
+		self.inner.consume();
+		self
+	}
+
 	#[inline]
 	unsafe fn is_valid_utf8() -> bool {
 		T::is_valid_utf8()
 	}
 
+	#[inline]
+	unsafe fn advances_by_one() -> bool {
+		T::advances_by_one()
+	}
+
 }
 
 
@@ -149,6 +266,133 @@ mod tests {
 
 	}
 
+	#[test]
+	fn recorder_depth() {
+
+		let mut parser = Parser::new( b"aaaabbb" );
+		assert_eq!( 0, parser.recorder_depth() );
+
+		let mut first_recorder = parser.record();
+		assert_eq!( 1, first_recorder.recorder_depth() );
+
+		let mut split = first_recorder.split_on_byte( b'b' );
+		let seg = split.next().unwrap();
+		assert_eq!( 1, seg.recorder_depth() );
+		assert_eq!( 2, seg.record().recorder_depth() );
+		assert_eq!( 2, seg.while_byte_fn( |_| true ).record().recorder_depth() );
+
+	}
+
+	#[test]
+	fn recorded_len() {
+
+		let mut parser = Parser::new( b"aaaabbb" );
+
+		let mut first_recorder = parser.record();
+		assert_eq!( 0, first_recorder.recorded_len() );
+		first_recorder.next();
+		assert_eq!( 1, first_recorder.recorded_len() );
+
+		{
+			let mut second_recorder = first_recorder.record();
+			assert_eq!( 0, second_recorder.recorded_len() );
+			second_recorder.consume_while_byte( b'a' );
+			assert_eq!( 3, second_recorder.recorded_len() );
+		}
+
+		assert_eq!( 4, first_recorder.recorded_len() );
+
+		first_recorder.consume();
+		assert_eq!( 7, first_recorder.recorded_len() );
+
+		// rewinding shrinks the recorded region
+		first_recorder.rewind( 2 ).unwrap();
+		assert_eq!( 5, first_recorder.recorded_len() );
+
+		// the split byte is not recorded
+		let mut parser = Parser::new( b"ab,c" );
+		let mut split = parser.split_on_byte( b',' );
+		let mut seg = split.next().unwrap().record();
+		seg.consume();
+		assert_eq!( 2, seg.recorded_len() );
+
+	}
+
+	#[test]
+	fn consumed() {
+
+		let mut parser = Parser::new( b"ab,cd" );
+		parser.next();
+
+		let mut rec = parser.record();
+		assert_eq!( 0, rec.consumed() );
+		rec.consume();
+		assert_eq!( 4, rec.consumed() );
+
+		// the split byte is not counted
+		let mut parser = Parser::new( b"ab,c" );
+		let mut split = parser.split_on_byte( b',' );
+		let mut seg = split.next().unwrap().record();
+		seg.consume();
+		assert_eq!( 2, seg.consumed() );
+		assert_eq!( seg.recorded_len(), seg.consumed() );
+
+	}
+
+	#[test]
+	fn trim() {
+
+		let is_space = |b: &u8| *b == b' ';
+
+		let mut parser = Parser::new( b"  a b  |" );
+		let mut rec = parser.record();
+		rec.consume_while_byte_fn( |&b| b != b'|' );
+		assert_eq!( b"a b  ", rec.trim_start_while(is_space).to_slice() );
+		assert_eq!( b"a b", rec.trim_end_while(is_space).to_slice() );
+		assert_eq!( 3, rec.recorded_len() );
+		assert_eq!( "a b", rec.to_str() );
+		// the position stays at the delimiter
+		assert_eq!( Some(b'|'), parser.next() );
+
+		// everything trimmed
+		let mut parser = Parser::new( b"   " );
+		let mut rec = parser.record();
+		rec.consume();
+		assert_eq!( b"", rec.trim_start_while(is_space).to_slice() );
+		assert_eq!( b"", rec.trim_end_while(is_space).to_slice() );
+		assert_eq!( 3, rec.offset() );
+
+		let mut parser = Parser::new( b"   " );
+		let mut rec = parser.record();
+		rec.consume();
+		assert_eq!( b"", rec.trim_end_while(is_space).to_slice() );
+		assert_eq!( 3, rec.offset() );
+
+		// consuming further discards the trim
+		let mut parser = Parser::new( b"a  b" );
+		let mut rec = parser.record();
+		rec.consume_len(3).unwrap();
+		assert_eq!( b"a", rec.trim_end_while(is_space).to_slice() );
+		assert_eq!( b"a", rec.trim_end_while(|_| false).to_slice() );
+		rec.next();
+		assert_eq!( b"a  b", rec.to_slice() );
+		rec.rewind(4).unwrap();
+		assert_eq!( b"", rec.to_slice() );
+
+		// nothing consumed
+		let mut parser = Parser::new( b" a" );
+		let mut rec = parser.record();
+		assert_eq!( b"", rec.trim_start_while(is_space).to_slice() );
+		assert_eq!( Some(b' '), rec.next() );
+
+	}
+
+	#[test]
+	#[should_panic]
+	fn recorded_len_without_recorder() {
+		Parser::new( b"a" ).recorded_len();
+	}
+
 	#[test]
 	fn check_that_it_is_inplace() {
 
@@ -0,0 +1,103 @@
+//!
+//! Splits the remaining slice at a given byte starting from the end.
+//!
+//! ## Example
+//! ```
+//! # use byte_parser::{StrParser, ParseIterator};
+//! let mut parser = StrParser::new("/usr/local/bin");
+//! let mut parts = parser.rsplit_on_byte(b'/');
+//!
+//! assert_eq!(parts.next(), Some(&b"bin"[..]));
+//! assert_eq!(parts.next(), Some(&b"local"[..]));
+//! assert_eq!(parts.next(), Some(&b"usr"[..]));
+//! assert_eq!(parts.next(), Some(&b""[..]));
+//! assert!(parts.next().is_none());
+//! ```
+
+
+/// Iterator returned by `rsplit_on_byte`.
+///
+/// Since a `ParseIterator` can only move forward the segments are returned
+/// as slices and not as iterators.
+#[derive(Debug, Clone)]
+pub struct RSplitOnByte<'s> {
+	// None if every segment was returned
+	slice: Option<&'s [u8]>,
+	byte: u8
+}
+
+impl<'s> RSplitOnByte<'s> {
+	pub(super) fn new(slice: &'s [u8], byte: u8) -> Self {
+		Self {
+			// an empty slice contains no segments, the same as split_on_byte
+			slice: Some(slice).filter(|s| !s.is_empty()),
+			byte
+		}
+	}
+}
+
+impl<'s> Iterator for RSplitOnByte<'s> {
+	type Item = &'s [u8];
+
+	fn next(&mut self) -> Option<&'s [u8]> {
+		let slice = self.slice?;
+		let byte = self.byte;
+
+		match slice.iter().rposition(|&b| b == byte) {
+			Some(i) => {
+				self.slice = Some(&slice[..i]);
+				Some(&slice[i + 1..])
+			},
+			None => {
+				self.slice = None;
+				Some(slice)
+			}
+		}
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+
+	use crate::*;
+
+	#[test]
+	fn same_as_rsplit() {
+
+		let cases = ["a.b.c", ".a", "a.", ".", "..", "abc"];
+
+		for s in cases.iter() {
+			let expected: Vec<_> = s.rsplit('.').map(str::as_bytes).collect();
+			let parts: Vec<_> = StrParser::new(s).rsplit_on_byte(b'.').collect();
+			assert_eq!(parts, expected, "{:?}", s);
+		}
+
+		assert!(StrParser::new("").rsplit_on_byte(b'.').next().is_none());
+
+	}
+
+	#[test]
+	fn split_last() {
+
+		let mut parser = StrParser::new("archive.tar.gz");
+		assert_eq!(
+			parser.split_last_on_byte(b'.'),
+			Some((&b"archive.tar"[..], &b"gz"[..]))
+		);
+		assert!(parser.is_empty());
+
+		// not found does not consume
+		let mut parser = StrParser::new("README");
+		assert!(parser.split_last_on_byte(b'.').is_none());
+		assert_eq!(parser.next(), Some(b'R'));
+
+		// respects the bounds of a segment
+		let mut parser = StrParser::new("a.b c.d");
+		let mut split = parser.split_on_byte(b' ');
+		let ab = split.next().unwrap();
+		assert_eq!(ab.split_last_on_byte(b'.'), Some((&b"a"[..], &b"b"[..])));
+
+	}
+
+}
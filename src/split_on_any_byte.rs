@@ -0,0 +1,318 @@
+//!
+//! Splits the iterator at any byte of a given set.
+//!
+//! ## Example
+//! ```
+//! # use byte_parser::{StrParser, ParseIterator};
+//! let mut parser = StrParser::new("a b,c\td");
+//! let parts: Vec<_> = parser
+//! 	.split_on_any_byte(b" ,\t")
+//! 	.map_and_collect(|part| part.record().consume_to_str());
+//!
+//! assert_eq!(parts, ["a", "b", "c", "d"]);
+//! ```
+
+
+use crate::{
+	ParseIterator,
+	recorder::Recorder,
+	position::Position,
+	pit::{PointInTime, ParserPointInTime},
+	byte_set::ByteSet
+};
+
+use core::iter;
+
+
+#[derive(Debug)]
+pub struct SplitOnAnyByte<'a, T> {
+	inner: SplitOnAnyByteIter<'a, T>
+}
+
+impl<'s, 'a, T> SplitOnAnyByte<'a, T>
+where T: ParseIterator<'s> {
+	pub(super) fn new(inner: &'a mut T, bytes: &[u8]) -> Self {
+		Self {
+			inner: SplitOnAnyByteIter::new(inner, ByteSet::from_slice(bytes))
+		}
+	}
+}
+
+impl<'s, 'a, T> SplitOnAnyByte<'a, T>
+where T: ParseIterator<'s> {
+
+	// next
+	// can't implement Iterator since the segment borrows self
+	#[allow(clippy::should_implement_trait)]
+	pub fn next(&mut self) -> Option<&mut SplitOnAnyByteIter<'a, T>> {
+		self.inner.reach_split_byte()?;
+		self.inner.state.record_pos = None;
+		self.inner.state.start = self.inner.state.pos;
+		self.inner.state.delimiter = None;
+
+		Some(&mut self.inner)
+	}
+
+	// for_each
+	pub fn for_each<F>(&mut self, mut f: F) -> &mut Self
+	where F: FnMut(&mut SplitOnAnyByteIter<'a, T>) {
+
+		let mut call_next = || {
+			f(self.next()?);
+			Some(())
+		};
+
+		// do while
+		while call_next().is_some() {}
+
+		self
+	}
+
+	// map
+	pub fn map_and_collect<F, A, B>(&mut self, mut f: F) -> B
+	where
+		F: FnMut(&mut SplitOnAnyByteIter<'a, T>) -> A,
+		B: iter::FromIterator<A> {
+		iter::from_fn(|| {
+			Some(f(self.next()?))
+		})
+		.collect()
+	}
+
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct SplitOnAnyByteState {
+	pos: Position,
+	// where the current segment started
+	start: Position,
+	byte_reached: bool,
+	delimiter: Option<u8>,
+	record_pos: Option<Position>// used so that we not return the split byte
+}
+
+impl PointInTime for SplitOnAnyByteState {
+
+	fn pos(&self) -> Position {
+		self.pos
+	}
+
+	unsafe fn set_pos(&mut self, pos: Position) {
+		self.pos = pos;
+	}
+
+	fn record_pos(&self) -> Position {
+		match self.record_pos {
+			Some(o) => o,
+			None => self.pos
+		}
+	}
+
+}
+
+/// Contains the point in time of the inner iterator, so restoring it also
+/// restores the state of the inner iterator.
+///
+/// `P` defaults to the point in time of `Parser` and `StrParser`, so the
+/// type can still be named without it when splitting those directly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SplitOnAnyBytePointInTime<P = ParserPointInTime> {
+	state: SplitOnAnyByteState,
+	inner: P
+}
+
+impl<P: PointInTime> PointInTime for SplitOnAnyBytePointInTime<P> {
+
+	fn pos(&self) -> Position {
+		self.state.pos()
+	}
+
+	unsafe fn set_pos(&mut self, pos: Position) {
+		self.state.set_pos(pos);
+		self.inner.set_pos(pos);
+	}
+
+	fn record_pos(&self) -> Position {
+		self.state.record_pos()
+	}
+
+}
+
+
+#[derive(Debug)]
+pub struct SplitOnAnyByteIter<'a, T> {
+	inner: &'a mut T,
+	bytes: ByteSet,
+	state: SplitOnAnyByteState
+}
+
+impl<'s, 'a, T> SplitOnAnyByteIter<'a, T>
+where T: ParseIterator<'s> {
+	pub(super) fn new(inner: &'a mut T, bytes: ByteSet) -> Self {
+
+		let state = SplitOnAnyByteState {
+			pos: inner.pit().pos(),
+			start: inner.pit().pos(),
+			// see SplitOnByteIter
+			byte_reached: inner.peek().is_some(),
+			delimiter: None,
+			record_pos: None
+		};
+
+		Self {inner, bytes, state}
+	}
+
+	pub(super) fn reach_split_byte(&mut self) -> Option<()> {
+
+		// reach the byte if not already reached
+		while self.advance().is_some() {}
+
+		if self.state.byte_reached {// reset byte_reached
+			self.state.byte_reached = false;
+			Some(())
+		} else { // we reached the end
+			None
+		}
+	}
+
+	/// Returns the delimiter which ended this segment.
+	///
+	/// Returns `None` if the segment was not consumed until the delimiter
+	/// or if the segment ended because no bytes are left.
+	pub fn delimiter(&self) -> Option<u8> {
+		self.state.delimiter
+	}
+}
+
+impl<'s, 'a, T> ParseIterator<'s> for SplitOnAnyByteIter<'a, T>
+where T: ParseIterator<'s> {
+
+	type PointInTime = SplitOnAnyBytePointInTime<T::PointInTime>;
+
+	// returns the full slice not only the split slice
+	fn slice(&self) -> &'s [u8] {
+		self.inner.slice()
+	}
+
+	fn pit(&self) -> Self::PointInTime {
+		SplitOnAnyBytePointInTime {
+			state: self.state,
+			inner: self.inner.pit()
+		}
+	}
+
+	fn restore_pit(&mut self, pit: Self::PointInTime) {
+		self.inner.restore_pit(pit.inner);
+		self.state = pit.state;
+	}
+
+	fn advance(&mut self) -> Option<()> {
+
+		if self.state.byte_reached {
+			return None
+		}
+
+		let start = self.inner.pit().pos();
+		self.inner.advance()?;
+
+		self.state.pos = self.inner.pit().pos();
+
+		let b = self.byte().unwrap();
+		if self.bytes.contains(b) {
+			self.state.byte_reached = true;
+			self.state.delimiter = Some(b);
+			self.state.record_pos = Some(start);
+			None
+		} else {
+			self.state.record_pos = None;
+			Some(())
+		}
+	}
+
+	fn recorder(&self) -> Option<&Recorder> {
+		self.inner.recorder()
+	}
+
+	fn recorder_depth(&self) -> usize {
+		self.inner.recorder_depth()
+	}
+
+	/// Does not allow to rewind before the start of the segment.
+	fn rewind(&mut self, len: usize) -> Result<&mut Self, usize> {
+		// the delimiter is not part of the segment
+		let end = self.state.record_pos() + 1;
+		let max = end - (self.state.start + 1);
+		if len > max {
+			return Err(max)
+		} else if len == 0 {
+			return Ok(self)
+		}
+
+		// the delimiter needs to be rewound as well if it was reached
+		let reached_len = (self.state.pos + 1) - end;
+		self.inner.rewind(len + reached_len)?;
+
+		self.state.pos = self.inner.pit().pos();
+		self.state.byte_reached = false;
+		self.state.record_pos = None;
+		self.state.delimiter = None;
+		Ok(self)
+	}
+
+	#[inline]
+	unsafe fn is_valid_utf8() -> bool {
+		T::is_valid_utf8()
+	}
+
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+	use crate::*;
+
+	#[test]
+	fn split_with_delimiters() {
+
+		let mut parser = StrParser::new("a b,,c\t");
+		let parts: Vec<_> = parser
+			.split_on_any_byte(b" ,\t")
+			.map_and_collect(|part| {
+				let s = part.record().consume_to_str();
+				(s, part.delimiter())
+			});
+
+		assert_eq!(parts, [
+			("a", Some(b' ')),
+			("b", Some(b',')),
+			("", Some(b',')),
+			("c", Some(b'\t')),
+			("", None)
+		]);
+
+	}
+
+	#[test]
+	fn delimiter_before_consumed() {
+
+		let mut parser = StrParser::new("ab;c");
+		let mut split = parser.split_on_any_byte(b";,");
+
+		let ab = split.next().unwrap();
+		assert!(ab.delimiter().is_none());
+		assert_eq!(ab.next(), Some(b'a'));
+		assert_eq!(ab.next(), Some(b'b'));
+		assert!(ab.next().is_none());
+		assert_eq!(ab.delimiter(), Some(b';'));
+
+		let c = split.next().unwrap();
+		assert!(c.delimiter().is_none());
+		assert_eq!(c.record().consume_to_str(), "c");
+		assert!(c.delimiter().is_none());
+		assert!(split.next().is_none());
+
+	}
+
+}
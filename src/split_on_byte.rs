@@ -16,30 +16,59 @@
 //! assert_eq!(world, "World!");
 //! assert!(splitter.next().is_none());
 //! ```
+//!
+//! ## Empty segments
+//! Every byte splits, so two bytes next to each other produce an empty
+//! segment and if the input ends with the byte an empty last segment is
+//! returned. `a,b,` returns `a`, `b` and an empty segment while an empty
+//! input returns no segment at all. Use `ended_with_delimiter` to
+//! differentiate `a,b,` from `a,b`.
 
 
 use crate::{
 	ParseIterator,
 	recorder::Recorder,
 	position::Position,
-	pit::PointInTime
+	pit::{PointInTime, ParserPointInTime},
+	byte_search::find_byte
 };
 
-use std::iter;
+use core::iter;
 
 
 #[derive(Debug)]
 pub struct SplitOnByte<'a, T> {
-	inner: SplitOnByteIter<'a, T>
+	inner: SplitOnByteIter<'a, T>,
+	// None means there is no limit
+	segments_left: Option<usize>,
+	// if the last reached byte was at the end of the input
+	delimiter_at_end: bool
 }
 
 impl<'s, 'a, T> SplitOnByte<'a, T>
 where T: ParseIterator<'s> {
 	pub(super) fn new(inner: &'a mut T, byte: u8) -> Self {
 		Self {
-			inner: SplitOnByteIter::new(inner, byte)
+			inner: SplitOnByteIter::new(inner, byte),
+			segments_left: None,
+			delimiter_at_end: false
 		}
 	}
+
+	pub(super) fn with_limit(inner: &'a mut T, n: usize, byte: u8) -> Self {
+		Self {
+			inner: SplitOnByteIter::new(inner, byte),
+			segments_left: Some(n),
+			delimiter_at_end: false
+		}
+	}
+
+	// splits on \n and \r\n (see lines)
+	pub(super) fn lines(inner: &'a mut T) -> Self {
+		let mut split = Self::new(inner, b'\n');
+		split.inner.strip_cr = true;
+		split
+	}
 }
 
 impl<'s, 'a, T> SplitOnByte<'a, T>
@@ -47,12 +76,75 @@ where T: ParseIterator<'s> {
 
 	// next
 	pub fn next(&mut self) -> Option<&mut SplitOnByteIter<'a, T>> {
+		if self.segments_left == Some(0) {
+			return None
+		}
+
 		self.inner.reach_split_byte()?;
-		self.inner.pit.record_pos = None;// can this break when we use revert?
+		self.inner.state.record_pos = None;// can this break when we use revert?
+		self.inner.state.start = self.inner.state.pos;
+		self.delimiter_at_end = self.inner.is_at_end();
+
+		if let Some(left) = &mut self.segments_left {
+			*left -= 1;
+			// the last segment contains the rest
+			self.inner.splitting = *left > 0;
+		}
 
 		Some(&mut self.inner)
 	}
 
+	/// Returns `true` if the input ended with the split byte, meaning the
+	/// last segment returned by `next` is the empty segment after it.
+	///
+	/// Is only final once `next` returned `None`.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("a,b,");
+	/// let mut split = parser.split_on_byte(b',');
+	/// let fields: Vec<_> = split.map_and_collect(|f| f.record().consume_to_str());
+	/// assert_eq!(fields, ["a", "b", ""]);
+	/// assert!(split.ended_with_delimiter());
+	/// ```
+	pub fn ended_with_delimiter(&self) -> bool {
+		self.delimiter_at_end
+	}
+
+	/// Returns only the last segment.
+	///
+	/// Since the iterator only moves forward every segment still needs to be
+	/// scanned, the last segment is scanned twice because the parser needs to
+	/// come back to its start.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("archive.tar.gz");
+	/// let ext = parser
+	/// 	.split_on_byte(b'.')
+	/// 	.last().unwrap()
+	/// 	.record()
+	/// 	.consume_to_str();
+	/// assert_eq!(ext, "gz");
+	/// ```
+	pub fn last(&mut self) -> Option<&mut SplitOnByteIter<'a, T>> {
+		self.next()?;
+
+		loop {
+			let pit = self.inner.pit();
+			let splitting = self.inner.splitting;
+
+			// if there is no next segment this is the last one
+			if self.next().is_none() {
+				self.inner.restore_pit(pit);
+				self.inner.splitting = splitting;
+				return Some(&mut self.inner)
+			}
+		}
+	}
+
 	// for_each
 	pub fn for_each<F>(&mut self, mut f: F) -> &mut Self
 	where F: FnMut(&mut SplitOnByteIter<'a, T>) {
@@ -68,6 +160,33 @@ where T: ParseIterator<'s> {
 		self
 	}
 
+	/// Like `for_each` but also passes the index of the segment.
+	///
+	/// When created with `splitn_on_byte` the last index belongs to the
+	/// segment containing the rest.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("a,b=c,d");
+	/// let mut fields = vec![];
+	/// parser.splitn_on_byte(2, b',')
+	/// 	.indexed_for_each(|i, p| {
+	/// 		fields.push((i, p.record().consume_to_str()));
+	/// 	});
+	/// assert_eq!(fields, [(0, "a"), (1, "b=c,d")]);
+	/// ```
+	pub fn indexed_for_each<F>(&mut self, mut f: F) -> &mut Self
+	where F: FnMut(usize, &mut SplitOnByteIter<'a, T>) {
+		let mut i = 0;
+		while let Some(segment) = self.next() {
+			f(i, segment);
+			i += 1;
+		}
+
+		self
+	}
+
 	// map
 	pub fn map_and_collect<F, A, B>(&mut self, mut f: F) -> B
 	where
@@ -79,16 +198,186 @@ where T: ParseIterator<'s> {
 		.collect()
 	}
 
+	/// Returns an `Iterator` which maps every segment with `f`, so the
+	/// standard iterator adapters can be used.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("a,,b,c");
+	/// let parts: Vec<_> = parser
+	/// 	.split_on_byte(b',')
+	/// 	.items(|p| p.record().consume_to_str())
+	/// 	.filter(|s| !s.is_empty())
+	/// 	.take(2)
+	/// 	.collect();
+	/// assert_eq!(parts, ["a", "b"]);
+	/// ```
+	pub fn items<F, O>(&mut self, f: F) -> Items<'_, 'a, T, F>
+	where F: FnMut(&mut SplitOnByteIter<'a, T>) -> O {
+		Items {split: self, f}
+	}
+
+	/// Like `map_and_collect` but stops at the first `Err` returned by `f`
+	/// and returns it.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("1,x,3");
+	/// let nums: Result<Vec<u32>, _> = parser
+	/// 	.split_on_byte(b',')
+	/// 	.try_map_and_collect(|p| p.record().consume_to_str().parse());
+	/// assert!(nums.is_err());
+	/// ```
+	pub fn try_map_and_collect<F, A, E, B>(&mut self, mut f: F) -> Result<B, E>
+	where
+		F: FnMut(&mut SplitOnByteIter<'a, T>) -> Result<A, E>,
+		B: iter::FromIterator<A> {
+		iter::from_fn(|| {
+			Some(f(self.next()?))
+		})
+		.collect()
+	}
+
+	/// Like `for_each` but stops at the first `Err` returned by `f` and
+	/// returns it. Later segments are not visited.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("1,2,x,3");
+	/// let mut sum = 0u32;
+	/// let res = parser
+	/// 	.split_on_byte(b',')
+	/// 	.try_for_each(|p| {
+	/// 		sum += p.record().consume_to_str().parse::<u32>()?;
+	/// 		Ok::<_, std::num::ParseIntError>(())
+	/// 	});
+	/// assert!(res.is_err());
+	/// assert_eq!(sum, 3);
+	/// ```
+	pub fn try_for_each<F, E>(&mut self, mut f: F) -> Result<(), E>
+	where F: FnMut(&mut SplitOnByteIter<'a, T>) -> Result<(), E> {
+		while let Some(segment) = self.next() {
+			f(segment)?;
+		}
+		Ok(())
+	}
+
+	/// Folds every segment into an accumulator, like `Iterator::fold`.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("1,2,3");
+	/// let sum = parser
+	/// 	.split_on_byte(b',')
+	/// 	.fold(0, |acc, p| {
+	/// 		acc + p.record().consume_to_str().parse::<u32>().unwrap()
+	/// 	});
+	/// assert_eq!(sum, 6);
+	/// ```
+	pub fn fold<B, F>(&mut self, init: B, mut f: F) -> B
+	where F: FnMut(B, &mut SplitOnByteIter<'a, T>) -> B {
+		let mut acc = init;
+		while let Some(segment) = self.next() {
+			acc = f(acc, segment);
+		}
+		acc
+	}
+
+	fn next_str(&mut self) -> Option<&'s str> {
+		Some(self.next()?.record().consume_to_str())
+	}
+
+	/// Returns the first two segments as `str`s, or `None` if there are
+	/// less than two segments.
+	///
+	/// Segments after the second one are ignored and not consumed, use
+	/// `splitn_on_byte` if the last field should contain the rest.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("key=value");
+	/// let (key, value) = parser.split_on_byte(b'=').collect_tuple2().unwrap();
+	/// assert_eq!((key, value), ("key", "value"));
+	///
+	/// let mut parser = StrParser::new("key");
+	/// assert!(parser.split_on_byte(b'=').collect_tuple2().is_none());
+	/// ```
+	///
+	/// ## Panics
+	/// Panics if a segment contains invalid utf8.
+	pub fn collect_tuple2(&mut self) -> Option<(&'s str, &'s str)> {
+		Some((self.next_str()?, self.next_str()?))
+	}
+
+	/// Returns the first three segments as `str`s, see `collect_tuple2`.
+	///
+	/// ## Panics
+	/// Panics if a segment contains invalid utf8.
+	pub fn collect_tuple3(&mut self) -> Option<(&'s str, &'s str, &'s str)> {
+		Some((self.next_str()?, self.next_str()?, self.next_str()?))
+	}
+
+	/// Returns the first four segments as `str`s, see `collect_tuple2`.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("10.0.0.1");
+	/// let ip = parser.split_on_byte(b'.').collect_tuple4().unwrap();
+	/// assert_eq!(ip, ("10", "0", "0", "1"));
+	/// ```
+	///
+	/// ## Panics
+	/// Panics if a segment contains invalid utf8.
+	pub fn collect_tuple4(
+		&mut self
+	) -> Option<(&'s str, &'s str, &'s str, &'s str)> {
+		Some((
+			self.next_str()?,
+			self.next_str()?,
+			self.next_str()?,
+			self.next_str()?
+		))
+	}
+
+}
+
+/// `Iterator` returned by `SplitOnByte::items`.
+#[derive(Debug)]
+pub struct Items<'b, 'a, T, F> {
+	split: &'b mut SplitOnByte<'a, T>,
+	f: F
+}
+
+impl<'s, 'b, 'a, T, F, O> Iterator for Items<'b, 'a, T, F>
+where
+	T: ParseIterator<'s>,
+	F: FnMut(&mut SplitOnByteIter<'a, T>) -> O {
+
+	type Item = O;
+
+	fn next(&mut self) -> Option<O> {
+		let segment = self.split.next()?;
+		Some((self.f)(segment))
+	}
+
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct SplitOnBytePointInTime {
+struct SplitOnByteState {
 	pos: Position,// this value should never be read unless it is returned from fn pit()
+	// where the current segment started
+	start: Position,
 	byte_reached: bool,
 	record_pos: Option<Position>// used so that we not return the split byte
 }
 
-impl PointInTime for SplitOnBytePointInTime {
+impl PointInTime for SplitOnByteState {
 
 	fn pos(&self) -> Position {
 		self.pos
@@ -107,20 +396,53 @@ impl PointInTime for SplitOnBytePointInTime {
 
 }
 
+/// Contains the point in time of the inner iterator, so restoring it also
+/// restores the state of the inner iterator.
+///
+/// `P` defaults to the point in time of `Parser` and `StrParser`, so the
+/// type can still be named without it when splitting those directly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SplitOnBytePointInTime<P = ParserPointInTime> {
+	state: SplitOnByteState,
+	inner: P
+}
+
+impl<P: PointInTime> PointInTime for SplitOnBytePointInTime<P> {
+
+	fn pos(&self) -> Position {
+		self.state.pos()
+	}
+
+	unsafe fn set_pos(&mut self, pos: Position) {
+		self.state.set_pos(pos);
+		self.inner.set_pos(pos);
+	}
+
+	fn record_pos(&self) -> Position {
+		self.state.record_pos()
+	}
+
+}
+
 
 #[derive(Debug)]
 pub struct SplitOnByteIter<'a, T> {
 	inner: &'a mut T,
 	byte: u8,
-	pit: SplitOnBytePointInTime
+	// false if the byte should not split anymore (see splitn_on_byte)
+	splitting: bool,
+	// if true a \r directly before the byte is part of the split (see lines)
+	pub(crate) strip_cr: bool,
+	state: SplitOnByteState
 }
 
 impl<'s, 'a, T> SplitOnByteIter<'a, T>
 where T: ParseIterator<'s> {
 	pub(super) fn new(inner: &'a mut T, byte: u8) -> Self {
 
-		let pit = SplitOnBytePointInTime {
+		let state = SplitOnByteState {
 			pos: inner.pit().pos(),
+			start: inner.pit().pos(),
 			// if the inner iterator has not already reached the end
 			// we want to set byte_reached to true so that we don't skip
 			// the first segment (part)
@@ -128,27 +450,97 @@ where T: ParseIterator<'s> {
 			record_pos: None
 		};
 
-		Self {inner, byte, pit}
+		Self {inner, byte, splitting: true, strip_cr: false, state}
 	}
 
 	pub(super) fn reach_split_byte(&mut self) -> Option<()> {
 
 		// reach the byte if not already reached
-		while let Some(_) = self.advance() {}
+		self.consume();
 
-		if self.pit.byte_reached {// reset byte_reached
-			self.pit.byte_reached = false;
+		if self.state.byte_reached {// reset byte_reached
+			self.state.byte_reached = false;
 			Some(())
 		} else { // we reached the end
 			None
 		}
 	}
+
+	// Searches the split byte in the slice and moves there directly,
+	// leaving the same state as calling advance until it returns None.
+	// Only valid if the inner iterator advances by one.
+	fn skip_to_split_byte(&mut self) {
+		if self.state.byte_reached {
+			return
+		}
+
+		let offset = self.inner.offset();
+		let slice = self.inner.slice();
+		let rest = &slice[offset..];
+		if rest.is_empty() {
+			return
+		}
+
+		let found = match self.splitting {
+			true => find_byte(self.byte, rest),
+			false => None
+		};
+
+		let pos: Position = match found {
+			Some(i) => (offset + i).into(),
+			None => (slice.len() - 1).into()
+		};
+
+		// safe because advances_by_one allows any position in the slice
+		unsafe {
+			let mut inner_pit = self.inner.pit();
+			inner_pit.set_pos(pos);
+			self.inner.restore_pit(inner_pit);
+		}
+
+		self.state.pos = pos;
+		if let Some(i) = found {
+			let cr = self.strip_cr && i > 0 && rest[i - 1] == b'\r';
+			self.state.byte_reached = true;
+			// the split byte (and \r) is not part of the segment
+			self.state.record_pos = Some(pos - if cr { 2 } else { 1 });
+		} else {
+			self.state.record_pos = None;
+		}
+	}
+
+	// returns true if nothing follows the current position
+	pub(crate) fn is_at_end(&mut self) -> bool {
+		self.inner.peek().is_none()
+	}
+
+	/// Returns how many bytes are left in this segment, without advancing.
+	///
+	/// ## Example
+	/// ```
+	/// # use byte_parser::{StrParser, ParseIterator};
+	/// let mut parser = StrParser::new("abc,de");
+	/// let mut split = parser.split_on_byte(b',');
+	/// let abc = split.next().unwrap();
+	/// assert_eq!(abc.segment_len(), 3);
+	/// abc.next();
+	/// assert_eq!(abc.segment_len(), 2);
+	/// assert_eq!(split.next().unwrap().segment_len(), 2);
+	/// ```
+	pub fn segment_len(&mut self) -> usize {
+		// the pit contains byte_reached and record_pos
+		// so restoring it undoes everything
+		let pit = self.pit();
+		let len = self.consume_and_count();
+		self.restore_pit(pit);
+		len
+	}
 }
 
 impl<'s, 'a, T> ParseIterator<'s> for SplitOnByteIter<'a, T>
 where T: ParseIterator<'s> {
 
-	type PointInTime = SplitOnBytePointInTime;
+	type PointInTime = SplitOnBytePointInTime<T::PointInTime>;
 
 	// returns the full slice not only the split slice
 	fn slice(&self) -> &'s [u8] {
@@ -156,37 +548,43 @@ where T: ParseIterator<'s> {
 	}
 
 	fn pit(&self) -> Self::PointInTime {
-		self.pit
+		SplitOnBytePointInTime {
+			state: self.state,
+			inner: self.inner.pit()
+		}
 	}
 
 	fn restore_pit(&mut self, pit: Self::PointInTime) {
-		// the inner pit doesnt know that the position changed
-		// safe because we just propagate our own position
-		unsafe {
-			let mut inner_pit = self.inner.pit();
-			inner_pit.set_pos(pit.pos());
-			self.inner.restore_pit(inner_pit);
-		}
-		self.pit = pit;
+		self.inner.restore_pit(pit.inner);
+		self.state = pit.state;
 	}
 
 	fn advance(&mut self) -> Option<()> {
 
-		if self.pit.byte_reached {
+		if self.state.byte_reached {
 			return None
 		}
 
 		let start = self.inner.pit().pos();
 		self.inner.advance()?;
 
-		self.pit.pos = self.inner.pit().pos();
+		self.state.pos = self.inner.pit().pos();
 
-		if self.byte().unwrap() == self.byte {
-			self.pit.byte_reached = true;
-			self.pit.record_pos = Some(start);
+		let b = self.byte().unwrap();
+		if self.splitting && b == self.byte {
+			self.state.byte_reached = true;
+			self.state.record_pos = Some(start);
+			None
+		} else if self.splitting && self.strip_cr && b == b'\r'
+			&& self.inner.peek() == Some(self.byte)
+		{
+			self.inner.advance();
+			self.state.pos = self.inner.pit().pos();
+			self.state.byte_reached = true;
+			self.state.record_pos = Some(start);
 			None
 		} else {
-			self.pit.record_pos = None;
+			self.state.record_pos = None;
 			Some(())
 		}
 	}
@@ -195,6 +593,40 @@ where T: ParseIterator<'s> {
 		self.inner.recorder()
 	}
 
+	fn recorder_depth(&self) -> usize {
+		self.inner.recorder_depth()
+	}
+
+	fn consume(&mut self) -> &mut Self {
+		if unsafe { T::advances_by_one() } {
+			self.skip_to_split_byte();
+		}
+
+		while self.advance().is_some() {}
+		self
+	}
+
+	/// Does not allow to rewind before the start of the segment.
+	fn rewind(&mut self, len: usize) -> Result<&mut Self, usize> {
+		// the split byte is not part of the segment
+		let end = self.state.record_pos() + 1;
+		let max = end - (self.state.start + 1);
+		if len > max {
+			return Err(max)
+		} else if len == 0 {
+			return Ok(self)
+		}
+
+		// the split byte needs to be rewound as well if it was reached
+		let reached_len = (self.state.pos + 1) - end;
+		self.inner.rewind(len + reached_len)?;
+
+		self.state.pos = self.inner.pit().pos();
+		self.state.byte_reached = false;
+		self.state.record_pos = None;
+		Ok(self)
+	}
+
 	#[inline]
 	unsafe fn is_valid_utf8() -> bool {
 		T::is_valid_utf8()
@@ -253,6 +685,123 @@ mod tests {
 
 	}
 
+	#[test]
+	fn items() {
+
+		let mut parser = StrParser::new("1,2,x,4");
+		let mut split = parser.split_on_byte(b',');
+		let nums: Vec<u8> = split
+			.items(|p| p.record().consume_to_str().parse().ok())
+			.take_while(Option::is_some)
+			.flatten()
+			.collect();
+		assert_eq!(nums, [1, 2]);
+		// the iterator stopped after x
+		assert_eq!(split.next().unwrap().record().consume_to_str(), "4");
+
+		let mut parser = StrParser::new("a,b");
+		let zipped: Vec<_> = parser.split_on_byte(b',')
+			.items(|p| p.record().consume_to_str())
+			.zip(1..)
+			.collect();
+		assert_eq!(zipped, [("a", 1), ("b", 2)]);
+
+	}
+
+	#[test]
+	fn indexed_for_each() {
+
+		let mut parser = StrParser::new("a,,c");
+		let mut segments = vec![];
+		parser.split_on_byte(b',')
+			.indexed_for_each(|i, p| {
+				segments.push((i, p.record().consume_to_str()));
+			});
+		assert_eq!(segments, [(0, "a"), (1, ""), (2, "c")]);
+
+	}
+
+	#[test]
+	fn try_map_and_collect() {
+
+		let mut parser = StrParser::new("1,2,3");
+		let nums: Result<Vec<u8>, _> = parser
+			.split_on_byte(b',')
+			.try_map_and_collect(|p| p.record().consume_to_str().parse());
+		assert_eq!(nums.unwrap(), [1, 2, 3]);
+
+		// stops at the first error
+		let mut parser = StrParser::new("1,x,3");
+		let mut visited = 0;
+		let nums: Result<Vec<u8>, _> = parser
+			.split_on_byte(b',')
+			.try_map_and_collect(|p| {
+				visited += 1;
+				p.record().consume_to_str().parse()
+			});
+		assert!(nums.is_err());
+		assert_eq!(visited, 2);
+		assert_eq!(parser.record().consume_to_str(), "3");
+
+	}
+
+	#[test]
+	fn try_for_each() {
+
+		let mut parser = StrParser::new("a,b,c");
+		let mut segments = vec![];
+		let res: Result<(), ()> = parser
+			.split_on_byte(b',')
+			.try_for_each(|p| {
+				segments.push(p.record().consume_to_str());
+				Ok(())
+			});
+		assert!(res.is_ok());
+		assert_eq!(segments, ["a", "b", "c"]);
+
+		// later segments are not visited
+		let mut parser = StrParser::new("a,,b,c");
+		let mut visited = 0;
+		let res = parser
+			.split_on_byte(b',')
+			.try_for_each(|p| {
+				visited += 1;
+				match p.record().consume_to_str() {
+					"" => Err("empty"),
+					_ => Ok(())
+				}
+			});
+		assert_eq!(res, Err("empty"));
+		assert_eq!(visited, 2);
+		assert_eq!(parser.record().consume_to_str(), "b,c");
+
+	}
+
+	#[test]
+	fn fold() {
+
+		use std::collections::HashMap;
+
+		let mut parser = StrParser::new("a=1;b=2;a=3");
+		let map = parser
+			.split_on_byte(b';')
+			.fold(HashMap::new(), |mut map, p| {
+				let mut kv = p.splitn_on_byte(2, b'=');
+				let k = kv.next().unwrap().record().consume_to_str();
+				let v = kv.next().unwrap().record().consume_to_str();
+				*map.entry(k).or_insert(0) += v.parse::<u32>().unwrap();
+				map
+			});
+		assert_eq!(map.len(), 2);
+		assert_eq!(map["a"], 4);
+		assert_eq!(map["b"], 2);
+
+		let mut parser = StrParser::new("");
+		let n = parser.split_on_byte(b',').fold(0, |n, _| n + 1);
+		assert_eq!(n, 0);
+
+	}
+
 	#[test]
 	fn if_peek_called_could_mess_up_byte_reached() {
 		// this test makes sure this doenst happen
@@ -273,6 +822,150 @@ mod tests {
 
 	}
 
+	#[test]
+	fn peek_at_the_end_of_a_nested_segment() {
+
+		// peeking past the end of the field reaches the end of the line
+		let mut parser = StrParser::new("a,b\nc,d");
+		let mut lines = parser.split_on_byte(b'\n');
+		let mut fields = vec![];
+		while let Some(line) = lines.next() {
+			let mut split = line.split_on_byte(b',');
+			while let Some(field) = split.next() {
+				assert!(field.peek_nth(5).is_none());
+				assert!(field.peek_len(5).is_none());
+				fields.push(field.record().consume_to_str());
+				// exhausted
+				assert!(field.peek().is_none());
+				assert!(field.peek_nth(1).is_none());
+			}
+		}
+		assert_eq!(fields, ["a", "b", "c", "d"]);
+
+		// peeking after the segment was exhausted
+		let mut parser = StrParser::new("ab\ncd");
+		let mut lines = parser.split_on_byte(b'\n');
+		let line = lines.next().unwrap();
+		let mut split = line.split_on_byte(b',');
+		let field = split.next().unwrap();
+		field.consume();
+		assert!(field.peek().is_none());
+		assert!(split.next().is_none());
+		assert_eq!(lines.next().unwrap().record().consume_to_str(), "cd");
+
+	}
+
+	#[test]
+	fn name_point_in_time_without_inner() {
+
+		use crate::split_on_byte::SplitOnBytePointInTime;
+
+		let mut parser = StrParser::new("a,b");
+		let mut split = parser.split_on_byte(b',');
+		let seg = split.next().unwrap();
+		let pit: SplitOnBytePointInTime = seg.pit();
+		seg.consume();
+		seg.restore_pit(pit);
+		assert_eq!(seg.record().consume_to_str(), "a");
+
+	}
+
+	#[test]
+	fn peek_through_wrappers() {
+
+		let mut parser = StrParser::new("a-b c\nd");
+		let mut lines = parser.split_on_byte(b'\n');
+		let line = lines.next().unwrap();
+		let mut words = line.split_on_byte(b' ');
+
+		let word = words.next().unwrap();
+		let mut word = word.ignore_byte(b'-');
+		assert_eq!(word.peek_nth(1), Some(b'b'));
+		assert!(word.peek_nth(2).is_none());
+		assert_eq!(word.next(), Some(b'a'));
+
+		let word = words.next().unwrap();
+		let mut word = word.while_byte_fn(u8::is_ascii_alphabetic);
+		assert!(word.peek_nth(3).is_none());
+		assert_eq!(word.consume_and_count(), 1);
+		assert!(word.peek().is_none());
+
+		assert!(words.next().is_none());
+		assert_eq!(lines.next().unwrap().record().consume_to_str(), "d");
+		assert!(lines.next().is_none());
+
+	}
+
+	#[test]
+	fn fast_path_same_as_slow_path() {
+
+		// split_on_fn has no fast path
+		fn slow(s: &str, n: usize) -> Vec<(String, Option<u8>)> {
+			StrParser::new(s)
+				.split_on_fn(|&b| b == b',')
+				.map_and_collect(|p| {
+					let part: String = p.bytes().take(n).map(char::from).collect();
+					let rest = p.record().consume_to_str().to_string();
+					(part + &rest, p.byte())
+				})
+		}
+
+		fn fast(s: &str, n: usize) -> Vec<(String, Option<u8>)> {
+			StrParser::new(s)
+				.split_on_byte(b',')
+				.map_and_collect(|p| {
+					let part: String = p.bytes().take(n).map(char::from).collect();
+					let rest = p.record().consume_to_str().to_string();
+					(part + &rest, p.byte())
+				})
+		}
+
+		let long = "a".repeat(20) + ",," + &"b".repeat(17) + "," + &"c".repeat(9);
+		let cases = ["", ",", "a", "a,", ",a", "ab,,cd,", long.as_str()];
+		for s in cases.iter() {
+			for n in 0..3 {
+				assert_eq!(fast(s, n), slow(s, n), "{:?} {}", s, n);
+			}
+		}
+
+		// skipped segments
+		let mut parser = StrParser::new(&long);
+		let mut split = parser.split_on_byte(b',');
+		split.next().unwrap();
+		split.next().unwrap();
+		let b = split.next().unwrap();
+		assert_eq!(b.rewind(1).unwrap_err(), 0);
+		assert_eq!(b.record().consume_to_str(), "b".repeat(17));
+		assert_eq!(split.last().unwrap().record().consume_to_str(), "c".repeat(9));
+
+	}
+
+	#[test]
+	fn segment_len() {
+
+		let mut parser = StrParser::new("ab,,cde");
+		let lens: Vec<_> = parser.split_on_byte(b',')
+			.map_and_collect(|p| {
+				let len = p.segment_len();
+				// the segment is still intact
+				assert_eq!(p.record().consume_to_str().len(), len);
+				len
+			});
+		assert_eq!(lens, [2, 0, 3]);
+
+		// peeking at the end of a segment doesn't reset byte_reached
+		let mut parser = StrParser::new("a,b");
+		let mut split = parser.split_on_byte(b',');
+		let a = split.next().unwrap();
+		a.consume();
+		assert_eq!(a.segment_len(), 0);
+		assert!(a.next().is_none());
+		let b = split.next().unwrap();
+		assert_eq!(b.segment_len(), 1);
+		assert!(split.next().is_none());
+
+	}
+
 	#[test]
 	fn return_empty_str() {
 		let mut s = StrParser::new("a ");
@@ -284,6 +977,33 @@ mod tests {
 		assert!(split.next().is_none());
 	}
 
+	#[test]
+	fn split_last() {
+
+		let mut s = StrParser::new("a.b.c");
+		let last = s.split_on_byte(b'.')
+			.last().unwrap()
+			.record().consume_to_str();
+		assert_eq!(last, "c");
+		assert!(s.next().is_none());
+
+		let mut s = StrParser::new("a.b.");
+		let mut split = s.split_on_byte(b'.');
+		let last = split.last().unwrap().record().consume_to_str();
+		assert_eq!(last, "");
+		assert!(split.next().is_none());
+
+		let mut s = StrParser::new("abc");
+		let last = s.split_on_byte(b'.')
+			.last().unwrap()
+			.record().consume_to_str();
+		assert_eq!(last, "abc");
+
+		let mut s = StrParser::new("");
+		assert!(s.split_on_byte(b'.').last().is_none());
+
+	}
+
 	#[test]
 	fn restoring_at_the_end_could_return_infinitely() {
 		let mut s = StrParser::new("a b");
@@ -299,4 +1019,128 @@ mod tests {
 		assert!(split.next().is_none());
 	}
 
+
+	#[test]
+	fn rewind_in_segment() {
+
+		let mut parser = StrParser::new("ab,cd");
+		let mut split = parser.split_on_byte(b',');
+
+		let ab = split.next().unwrap();
+		ab.consume();
+		// can't go before the segment
+		assert_eq!(ab.rewind(3).unwrap_err(), 2);
+		ab.rewind(1).unwrap();
+		assert_eq!(ab.record().consume_to_str(), "b");
+
+		let cd = split.next().unwrap();
+		assert_eq!(cd.rewind(1).unwrap_err(), 0);
+		cd.next();
+		cd.rewind(1).unwrap();
+		assert_eq!(cd.record().consume_to_str(), "cd");
+
+		assert!(split.next().is_none());
+
+	}
+
+
+	#[test]
+	fn ended_with_delimiter() {
+
+		let collect = |s: &'static str| {
+			let mut parser = StrParser::new(s);
+			let mut split = parser.split_on_byte(b',');
+			let parts: Vec<_> = split.map_and_collect(|p| p.record().consume_to_str());
+			(parts, split.ended_with_delimiter())
+		};
+
+		assert_eq!(collect("a,b,"), (vec!["a", "b", ""], true));
+		assert_eq!(collect("a,b"), (vec!["a", "b"], false));
+		assert_eq!(collect(","), (vec!["", ""], true));
+		assert_eq!(collect("a,,"), (vec!["a", "", ""], true));
+		assert_eq!(collect(""), (vec![], false));
+
+		// a segment which is not fully consumed
+		let mut parser = StrParser::new("ab,");
+		let mut split = parser.split_on_byte(b',');
+		split.next().unwrap().next();
+		assert!(!split.ended_with_delimiter());
+		assert_eq!(split.next().unwrap().segment_len(), 0);
+		assert!(split.ended_with_delimiter());
+		assert!(split.next().is_none());
+		assert!(split.ended_with_delimiter());
+
+		// the last segment of splitn is not split
+		let mut parser = StrParser::new("a,b,");
+		let mut split = parser.splitn_on_byte(2, b',');
+		assert_eq!(split.next().unwrap().record().consume_to_str(), "a");
+		assert_eq!(split.next().unwrap().record().consume_to_str(), "b,");
+		assert!(split.next().is_none());
+		assert!(!split.ended_with_delimiter());
+
+	}
+
+	#[test]
+	fn collect_tuple() {
+
+		let mut parser = StrParser::new("a,b,c,d,e");
+		let mut split = parser.split_on_byte(b',');
+		assert_eq!(split.collect_tuple2(), Some(("a", "b")));
+		// continues after the collected segments
+		assert_eq!(split.collect_tuple3(), Some(("c", "d", "e")));
+		assert!(split.collect_tuple2().is_none());
+
+		let mut parser = StrParser::new("a,,");
+		assert_eq!(parser.split_on_byte(b',').collect_tuple3(), Some(("a", "", "")));
+
+		let mut parser = StrParser::new("a,b,c");
+		assert!(parser.split_on_byte(b',').collect_tuple4().is_none());
+
+		let mut parser = StrParser::new("");
+		assert!(parser.split_on_byte(b',').collect_tuple2().is_none());
+
+	}
+
+	#[test]
+	fn splitn() {
+
+		fn splitn(s: &str, n: usize) -> Vec<&str> {
+			StrParser::new(s)
+				.splitn_on_byte(n, b'=')
+				.map_and_collect(|p| p.record().consume_to_str())
+		}
+
+		let cases = [
+			("a=b=c", 0),
+			("a=b=c", 1),
+			("a=b=c", 2),
+			("a=b=c", 3),
+			("a=b=c", 4),
+			("=", 1),
+			("=", 2),
+			("a", 2),
+			("a==", 2)
+		];
+
+		for (s, n) in cases.iter() {
+			let expected: Vec<_> = s.splitn(*n, '=').collect();
+			assert_eq!(splitn(s, *n), expected, "{:?} splitn {}", s, n);
+		}
+
+		// partially consumed segments
+		let mut parser = StrParser::new("a=b=c");
+		let mut split = parser.splitn_on_byte(2, b'=');
+		assert_eq!(split.next().unwrap().next(), Some(b'a'));
+		assert_eq!(split.next().unwrap().next(), Some(b'b'));
+		assert!(split.next().is_none());
+
+		let mut parser = StrParser::new("a=b=c");
+		let last = parser.splitn_on_byte(2, b'=')
+			.last().unwrap()
+			.record()
+			.consume_to_str();
+		assert_eq!(last, "b=c");
+
+	}
+
 }
\ No newline at end of file
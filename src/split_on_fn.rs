@@ -0,0 +1,300 @@
+//!
+//! Splits the iterator at every byte for which a function returns `true`.
+//!
+//! ## Example
+//! ```
+//! # use byte_parser::{StrParser, ParseIterator};
+//! let mut parser = StrParser::new("a b\tc\nd");
+//! let parts: Vec<_> = parser
+//! 	.split_on_fn(u8::is_ascii_whitespace)
+//! 	.map_and_collect(|part| part.record().consume_to_str());
+//!
+//! assert_eq!(parts, ["a", "b", "c", "d"]);
+//! ```
+
+
+use crate::{
+	ParseIterator,
+	recorder::Recorder,
+	position::Position,
+	pit::{PointInTime, ParserPointInTime}
+};
+
+use core::iter;
+
+
+#[derive(Debug)]
+pub struct SplitOnFn<'a, T, F> {
+	inner: SplitOnFnIter<'a, T, F>
+}
+
+impl<'s, 'a, T, F> SplitOnFn<'a, T, F>
+where
+	T: ParseIterator<'s>,
+	F: Fn(&u8) -> bool {
+	pub(super) fn new(inner: &'a mut T, f: F) -> Self {
+		Self {
+			inner: SplitOnFnIter::new(inner, f)
+		}
+	}
+}
+
+impl<'s, 'a, T, F> SplitOnFn<'a, T, F>
+where
+	T: ParseIterator<'s>,
+	F: Fn(&u8) -> bool {
+
+	// next
+	// can't implement Iterator since the segment borrows self
+	#[allow(clippy::should_implement_trait)]
+	pub fn next(&mut self) -> Option<&mut SplitOnFnIter<'a, T, F>> {
+		self.inner.reach_split_byte()?;
+		self.inner.state.record_pos = None;
+		self.inner.state.start = self.inner.state.pos;
+
+		Some(&mut self.inner)
+	}
+
+	// for_each
+	pub fn for_each<E>(&mut self, mut each: E) -> &mut Self
+	where E: FnMut(&mut SplitOnFnIter<'a, T, F>) {
+
+		let mut call_next = || {
+			each(self.next()?);
+			Some(())
+		};
+
+		// do while
+		while call_next().is_some() {}
+
+		self
+	}
+
+	// map
+	pub fn map_and_collect<M, A, B>(&mut self, mut map: M) -> B
+	where
+		M: FnMut(&mut SplitOnFnIter<'a, T, F>) -> A,
+		B: iter::FromIterator<A> {
+		iter::from_fn(|| {
+			Some(map(self.next()?))
+		})
+		.collect()
+	}
+
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct SplitOnFnState {
+	pos: Position,
+	// where the current segment started
+	start: Position,
+	byte_reached: bool,
+	record_pos: Option<Position>// used so that we not return the split byte
+}
+
+impl PointInTime for SplitOnFnState {
+
+	fn pos(&self) -> Position {
+		self.pos
+	}
+
+	unsafe fn set_pos(&mut self, pos: Position) {
+		self.pos = pos;
+	}
+
+	fn record_pos(&self) -> Position {
+		match self.record_pos {
+			Some(o) => o,
+			None => self.pos
+		}
+	}
+
+}
+
+/// Contains the point in time of the inner iterator, so restoring it also
+/// restores the state of the inner iterator.
+///
+/// `P` defaults to the point in time of `Parser` and `StrParser`, so the
+/// type can still be named without it when splitting those directly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SplitOnFnPointInTime<P = ParserPointInTime> {
+	state: SplitOnFnState,
+	inner: P
+}
+
+impl<P: PointInTime> PointInTime for SplitOnFnPointInTime<P> {
+
+	fn pos(&self) -> Position {
+		self.state.pos()
+	}
+
+	unsafe fn set_pos(&mut self, pos: Position) {
+		self.state.set_pos(pos);
+		self.inner.set_pos(pos);
+	}
+
+	fn record_pos(&self) -> Position {
+		self.state.record_pos()
+	}
+
+}
+
+
+#[derive(Debug)]
+pub struct SplitOnFnIter<'a, T, F> {
+	inner: &'a mut T,
+	f: F,
+	state: SplitOnFnState
+}
+
+impl<'s, 'a, T, F> SplitOnFnIter<'a, T, F>
+where
+	T: ParseIterator<'s>,
+	F: Fn(&u8) -> bool {
+	pub(super) fn new(inner: &'a mut T, f: F) -> Self {
+
+		let state = SplitOnFnState {
+			pos: inner.pit().pos(),
+			start: inner.pit().pos(),
+			// see SplitOnByteIter
+			byte_reached: inner.peek().is_some(),
+			record_pos: None
+		};
+
+		Self {inner, f, state}
+	}
+
+	pub(super) fn reach_split_byte(&mut self) -> Option<()> {
+
+		// reach the byte if not already reached
+		while self.advance().is_some() {}
+
+		if self.state.byte_reached {// reset byte_reached
+			self.state.byte_reached = false;
+			Some(())
+		} else { // we reached the end
+			None
+		}
+	}
+}
+
+impl<'s, 'a, T, F> ParseIterator<'s> for SplitOnFnIter<'a, T, F>
+where
+	T: ParseIterator<'s>,
+	F: Fn(&u8) -> bool {
+
+	type PointInTime = SplitOnFnPointInTime<T::PointInTime>;
+
+	// returns the full slice not only the split slice
+	fn slice(&self) -> &'s [u8] {
+		self.inner.slice()
+	}
+
+	fn pit(&self) -> Self::PointInTime {
+		SplitOnFnPointInTime {
+			state: self.state,
+			inner: self.inner.pit()
+		}
+	}
+
+	fn restore_pit(&mut self, pit: Self::PointInTime) {
+		self.inner.restore_pit(pit.inner);
+		self.state = pit.state;
+	}
+
+	fn advance(&mut self) -> Option<()> {
+
+		if self.state.byte_reached {
+			return None
+		}
+
+		let start = self.inner.pit().pos();
+		self.inner.advance()?;
+
+		self.state.pos = self.inner.pit().pos();
+
+		if (self.f)(&self.byte().unwrap()) {
+			self.state.byte_reached = true;
+			self.state.record_pos = Some(start);
+			None
+		} else {
+			self.state.record_pos = None;
+			Some(())
+		}
+	}
+
+	fn recorder(&self) -> Option<&Recorder> {
+		self.inner.recorder()
+	}
+
+	fn recorder_depth(&self) -> usize {
+		self.inner.recorder_depth()
+	}
+
+	/// Does not allow to rewind before the start of the segment.
+	fn rewind(&mut self, len: usize) -> Result<&mut Self, usize> {
+		// the split byte is not part of the segment
+		let end = self.state.record_pos() + 1;
+		let max = end - (self.state.start + 1);
+		if len > max {
+			return Err(max)
+		} else if len == 0 {
+			return Ok(self)
+		}
+
+		// the split byte needs to be rewound as well if it was reached
+		let reached_len = (self.state.pos + 1) - end;
+		self.inner.rewind(len + reached_len)?;
+
+		self.state.pos = self.inner.pit().pos();
+		self.state.byte_reached = false;
+		self.state.record_pos = None;
+		Ok(self)
+	}
+
+	#[inline]
+	unsafe fn is_valid_utf8() -> bool {
+		T::is_valid_utf8()
+	}
+
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+	use crate::*;
+
+	#[test]
+	fn split_on_whitespace() {
+
+		let mut parser = StrParser::new("a b\t\tc\n");
+		let parts: Vec<_> = parser
+			.split_on_fn(u8::is_ascii_whitespace)
+			.map_and_collect(|part| part.record().consume_to_str());
+
+		assert_eq!(parts, ["a", "b", "", "c", ""]);
+
+	}
+
+	#[test]
+	fn combined_with_ignore_byte() {
+
+		let mut parser = StrParser::new("1_000,2_0;3");
+		let mut c = 0;
+		parser
+			.split_on_fn(|&b| b == b',' || b == b';')
+			.for_each(|part| {
+				c += 1;
+				let num = part
+					.ignore_byte(b'_')
+					.while_byte_fn(u8::is_ascii_digit)
+					.consume_and_count();
+				assert!(num > 0);
+			});
+		assert_eq!(c, 3);
+
+	}
+
+}
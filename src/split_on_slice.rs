@@ -0,0 +1,355 @@
+//!
+//! Splits the iterator at a given slice.
+//!
+//! ## Example
+//! ```
+//! # use byte_parser::{StrParser, ParseIterator};
+//! let mut parser = StrParser::new("Hello\r\nWorld!");
+//! let mut splitter = parser.split_on_slice(b"\r\n");
+//!
+//! let hello = splitter.next().unwrap()
+//! 	.record().consume_to_str();
+//! let world = splitter.next().unwrap()
+//! 	.record().consume_to_str();
+//!
+//! assert_eq!(hello, "Hello");
+//! assert_eq!(world, "World!");
+//! assert!(splitter.next().is_none());
+//! ```
+
+
+use crate::{
+	ParseIterator,
+	recorder::Recorder,
+	position::Position,
+	pit::{PointInTime, ParserPointInTime}
+};
+
+use core::iter;
+
+
+#[derive(Debug)]
+pub struct SplitOnSlice<'a, T> {
+	inner: SplitOnSliceIter<'a, T>
+}
+
+impl<'s, 'a, T> SplitOnSlice<'a, T>
+where T: ParseIterator<'s> {
+	/// ## Panics
+	/// If the needle is empty.
+	pub(super) fn new(inner: &'a mut T, needle: &'a [u8]) -> Self {
+		Self {
+			inner: SplitOnSliceIter::new(inner, needle)
+		}
+	}
+}
+
+impl<'s, 'a, T> SplitOnSlice<'a, T>
+where T: ParseIterator<'s> {
+
+	// next
+	// can't implement Iterator since the segment borrows self
+	#[allow(clippy::should_implement_trait)]
+	pub fn next(&mut self) -> Option<&mut SplitOnSliceIter<'a, T>> {
+		self.inner.reach_needle()?;
+		self.inner.state.record_pos = None;
+		self.inner.state.start = self.inner.state.pos;
+
+		Some(&mut self.inner)
+	}
+
+	// for_each
+	pub fn for_each<F>(&mut self, mut f: F) -> &mut Self
+	where F: FnMut(&mut SplitOnSliceIter<'a, T>) {
+
+		let mut call_next = || {
+			f(self.next()?);
+			Some(())
+		};
+
+		// do while
+		while call_next().is_some() {}
+
+		self
+	}
+
+	// map
+	pub fn map_and_collect<F, A, B>(&mut self, mut f: F) -> B
+	where
+		F: FnMut(&mut SplitOnSliceIter<'a, T>) -> A,
+		B: iter::FromIterator<A> {
+		iter::from_fn(|| {
+			Some(f(self.next()?))
+		})
+		.collect()
+	}
+
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct SplitOnSliceState {
+	pos: Position,
+	// where the current segment started
+	start: Position,
+	needle_reached: bool,
+	record_pos: Option<Position>// used so that we not return the needle
+}
+
+impl PointInTime for SplitOnSliceState {
+
+	fn pos(&self) -> Position {
+		self.pos
+	}
+
+	unsafe fn set_pos(&mut self, pos: Position) {
+		self.pos = pos;
+	}
+
+	fn record_pos(&self) -> Position {
+		match self.record_pos {
+			Some(o) => o,
+			None => self.pos
+		}
+	}
+
+}
+
+/// Contains the point in time of the inner iterator, so restoring it also
+/// restores the state of the inner iterator.
+///
+/// `P` defaults to the point in time of `Parser` and `StrParser`, so the
+/// type can still be named without it when splitting those directly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SplitOnSlicePointInTime<P = ParserPointInTime> {
+	state: SplitOnSliceState,
+	inner: P
+}
+
+impl<P: PointInTime> PointInTime for SplitOnSlicePointInTime<P> {
+
+	fn pos(&self) -> Position {
+		self.state.pos()
+	}
+
+	unsafe fn set_pos(&mut self, pos: Position) {
+		self.state.set_pos(pos);
+		self.inner.set_pos(pos);
+	}
+
+	fn record_pos(&self) -> Position {
+		self.state.record_pos()
+	}
+
+}
+
+
+#[derive(Debug)]
+pub struct SplitOnSliceIter<'a, T> {
+	inner: &'a mut T,
+	needle: &'a [u8],
+	state: SplitOnSliceState
+}
+
+impl<'s, 'a, T> SplitOnSliceIter<'a, T>
+where T: ParseIterator<'s> {
+	pub(super) fn new(inner: &'a mut T, needle: &'a [u8]) -> Self {
+		assert!(!needle.is_empty(), "needle cannot be empty");
+
+		let state = SplitOnSliceState {
+			pos: inner.pit().pos(),
+			start: inner.pit().pos(),
+			// see SplitOnByteIter
+			needle_reached: inner.peek().is_some(),
+			record_pos: None
+		};
+
+		Self {inner, needle, state}
+	}
+
+	pub(super) fn reach_needle(&mut self) -> Option<()> {
+
+		// reach the needle if not already reached
+		while self.advance().is_some() {}
+
+		if self.state.needle_reached {// reset needle_reached
+			self.state.needle_reached = false;
+			Some(())
+		} else { // we reached the end
+			None
+		}
+	}
+}
+
+impl<'s, 'a, T> ParseIterator<'s> for SplitOnSliceIter<'a, T>
+where T: ParseIterator<'s> {
+
+	type PointInTime = SplitOnSlicePointInTime<T::PointInTime>;
+
+	// returns the full slice not only the split slice
+	fn slice(&self) -> &'s [u8] {
+		self.inner.slice()
+	}
+
+	fn pit(&self) -> Self::PointInTime {
+		SplitOnSlicePointInTime {
+			state: self.state,
+			inner: self.inner.pit()
+		}
+	}
+
+	fn restore_pit(&mut self, pit: Self::PointInTime) {
+		self.inner.restore_pit(pit.inner);
+		self.state = pit.state;
+	}
+
+	fn advance(&mut self) -> Option<()> {
+
+		if self.state.needle_reached {
+			return None
+		}
+
+		let start = self.inner.pit().pos();
+
+		if self.inner.starts_with(self.needle) {
+			// skip the needle
+			self.inner.consume_len(self.needle.len()).ok()?;
+			self.state.pos = self.inner.pit().pos();
+			self.state.needle_reached = true;
+			self.state.record_pos = Some(start);
+			return None
+		}
+
+		self.inner.advance()?;
+		self.state.pos = self.inner.pit().pos();
+		self.state.record_pos = None;
+		Some(())
+	}
+
+	fn recorder(&self) -> Option<&Recorder> {
+		self.inner.recorder()
+	}
+
+	fn recorder_depth(&self) -> usize {
+		self.inner.recorder_depth()
+	}
+
+	/// Does not allow to rewind before the start of the segment.
+	fn rewind(&mut self, len: usize) -> Result<&mut Self, usize> {
+		// the needle is not part of the segment
+		let end = self.state.record_pos() + 1;
+		let max = end - (self.state.start + 1);
+		if len > max {
+			return Err(max)
+		} else if len == 0 {
+			return Ok(self)
+		}
+
+		// the needle needs to be rewound as well if it was reached
+		let reached_len = (self.state.pos + 1) - end;
+		self.inner.rewind(len + reached_len)?;
+
+		self.state.pos = self.inner.pit().pos();
+		self.state.needle_reached = false;
+		self.state.record_pos = None;
+		Ok(self)
+	}
+
+	#[inline]
+	unsafe fn is_valid_utf8() -> bool {
+		T::is_valid_utf8()
+	}
+
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+	use crate::*;
+
+	fn split(s: &str, needle: &str) -> Vec<String> {
+		StrParser::new(s)
+			.split_on_slice(needle.as_bytes())
+			.map_and_collect(|p| p.record().consume_to_str().to_string())
+	}
+
+	#[test]
+	fn same_as_str_split() {
+
+		let cases = [
+			("a-->b-->c", "-->"),
+			("-->a", "-->"),
+			("a-->", "-->"),
+			("-->", "-->"),
+			("a--b->c", "-->"),
+			("a---->b", "-->"),
+			("ababa", "aba"),
+			("abababa", "aba"),
+			("xabaabay", "aba"),
+			("abc", "x"),
+			("a", "abc"),
+			("a\r\n\r\nb", "\r\n")
+		];
+
+		for (s, needle) in cases.iter() {
+			let expected: Vec<_> = s.split(needle).collect();
+			assert_eq!(split(s, needle), expected, "{:?} split {:?}", s, needle);
+		}
+
+	}
+
+	#[test]
+	fn empty_input() {
+
+		assert!(split("", "ab").is_empty());
+
+	}
+
+	#[test]
+	fn next_and_peek() {
+
+		let mut parser = StrParser::new("ab::cd");
+		let mut split = parser.split_on_slice(b"::");
+
+		let ab = split.next().unwrap();
+		assert_eq!(ab.next(), Some(b'a'));
+		assert_eq!(ab.peek(), Some(b'b'));
+		assert_eq!(ab.next(), Some(b'b'));
+		assert!(ab.peek().is_none());
+		assert!(ab.next().is_none());
+
+		let cd = split.next().unwrap();
+		assert_eq!(cd.record().consume_to_str(), "cd");
+
+		assert!(split.next().is_none());
+
+	}
+
+	#[test]
+	#[should_panic]
+	fn empty_needle() {
+
+		let mut parser = StrParser::new("ab");
+		let _ = parser.split_on_slice(b"");
+
+	}
+
+
+	#[test]
+	fn rewind_over_needle() {
+
+		let mut parser = StrParser::new("ab::cd");
+		let mut split = parser.split_on_slice(b"::");
+
+		let ab = split.next().unwrap();
+		ab.consume();
+		ab.rewind(2).unwrap();
+		assert_eq!(ab.record().consume_to_str(), "ab");
+
+		let cd = split.next().unwrap();
+		assert_eq!(cd.record().consume_to_str(), "cd");
+
+	}
+
+}
@@ -0,0 +1,333 @@
+//!
+//! Splits the iterator at a byte, except if the byte is inside quotes.
+//!
+//! The quotes are part of the segments. A doubled quote inside quotes
+//! (like `"a ""quoted"" word"`) leaves and directly reenters the quotes,
+//! so it is handled like an escaped quote.
+//!
+//! ## Example
+//! ```
+//! # use byte_parser::{StrParser, ParseIterator};
+//! let mut parser = StrParser::new(r#"a,"b,c","say ""hi""""#);
+//! let fields: Vec<_> = parser
+//! 	.split_on_byte_respecting(b',', b'"')
+//! 	.map_and_collect(|field| field.record().consume_to_str());
+//!
+//! assert_eq!(fields, ["a", r#""b,c""#, r#""say ""hi""""#]);
+//! ```
+
+
+use crate::{
+	ParseIterator,
+	recorder::Recorder,
+	position::Position,
+	pit::{PointInTime, ParserPointInTime}
+};
+
+use core::iter;
+
+
+#[derive(Debug)]
+pub struct SplitRespecting<'a, T> {
+	inner: SplitRespectingIter<'a, T>
+}
+
+impl<'s, 'a, T> SplitRespecting<'a, T>
+where T: ParseIterator<'s> {
+	pub(super) fn new(inner: &'a mut T, byte: u8, quote: u8) -> Self {
+		Self {
+			inner: SplitRespectingIter::new(inner, byte, quote)
+		}
+	}
+}
+
+impl<'s, 'a, T> SplitRespecting<'a, T>
+where T: ParseIterator<'s> {
+
+	// next
+	// can't implement Iterator since the segment borrows self
+	#[allow(clippy::should_implement_trait)]
+	pub fn next(&mut self) -> Option<&mut SplitRespectingIter<'a, T>> {
+		self.inner.reach_split_byte()?;
+		self.inner.state.record_pos = None;
+		self.inner.state.start = self.inner.state.pos;
+		self.inner.state.in_quotes = false;
+
+		Some(&mut self.inner)
+	}
+
+	// for_each
+	pub fn for_each<E>(&mut self, mut each: E) -> &mut Self
+	where E: FnMut(&mut SplitRespectingIter<'a, T>) {
+
+		let mut call_next = || {
+			each(self.next()?);
+			Some(())
+		};
+
+		// do while
+		while call_next().is_some() {}
+
+		self
+	}
+
+	// map
+	pub fn map_and_collect<M, A, B>(&mut self, mut map: M) -> B
+	where
+		M: FnMut(&mut SplitRespectingIter<'a, T>) -> A,
+		B: iter::FromIterator<A> {
+		iter::from_fn(|| {
+			Some(map(self.next()?))
+		})
+		.collect()
+	}
+
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct SplitRespectingState {
+	pos: Position,
+	// where the current segment started
+	start: Position,
+	in_quotes: bool,
+	byte_reached: bool,
+	record_pos: Option<Position>// used so that we not return the split byte
+}
+
+impl PointInTime for SplitRespectingState {
+
+	fn pos(&self) -> Position {
+		self.pos
+	}
+
+	unsafe fn set_pos(&mut self, pos: Position) {
+		self.pos = pos;
+	}
+
+	fn record_pos(&self) -> Position {
+		match self.record_pos {
+			Some(o) => o,
+			None => self.pos
+		}
+	}
+
+}
+
+/// Contains the point in time of the inner iterator, so restoring it also
+/// restores the state of the inner iterator.
+///
+/// `P` defaults to the point in time of `Parser` and `StrParser`, so the
+/// type can still be named without it when splitting those directly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SplitRespectingPointInTime<P = ParserPointInTime> {
+	state: SplitRespectingState,
+	inner: P
+}
+
+impl<P: PointInTime> PointInTime for SplitRespectingPointInTime<P> {
+
+	fn pos(&self) -> Position {
+		self.state.pos()
+	}
+
+	unsafe fn set_pos(&mut self, pos: Position) {
+		self.state.set_pos(pos);
+		self.inner.set_pos(pos);
+	}
+
+	fn record_pos(&self) -> Position {
+		self.state.record_pos()
+	}
+
+}
+
+
+#[derive(Debug)]
+pub struct SplitRespectingIter<'a, T> {
+	inner: &'a mut T,
+	byte: u8,
+	quote: u8,
+	state: SplitRespectingState
+}
+
+impl<'s, 'a, T> SplitRespectingIter<'a, T>
+where T: ParseIterator<'s> {
+	pub(super) fn new(inner: &'a mut T, byte: u8, quote: u8) -> Self {
+
+		let state = SplitRespectingState {
+			pos: inner.pit().pos(),
+			start: inner.pit().pos(),
+			in_quotes: false,
+			// see SplitOnByteIter
+			byte_reached: inner.peek().is_some(),
+			record_pos: None
+		};
+
+		Self {inner, byte, quote, state}
+	}
+
+	pub(super) fn reach_split_byte(&mut self) -> Option<()> {
+
+		// reach the byte if not already reached
+		while self.advance().is_some() {}
+
+		if self.state.byte_reached {// reset byte_reached
+			self.state.byte_reached = false;
+			Some(())
+		} else { // we reached the end
+			None
+		}
+	}
+
+	/// Returns `true` if the next byte is inside quotes.
+	pub fn in_quotes(&self) -> bool {
+		self.state.in_quotes
+	}
+}
+
+impl<'s, 'a, T> ParseIterator<'s> for SplitRespectingIter<'a, T>
+where T: ParseIterator<'s> {
+
+	type PointInTime = SplitRespectingPointInTime<T::PointInTime>;
+
+	// returns the full slice not only the split slice
+	fn slice(&self) -> &'s [u8] {
+		self.inner.slice()
+	}
+
+	fn pit(&self) -> Self::PointInTime {
+		SplitRespectingPointInTime {
+			state: self.state,
+			inner: self.inner.pit()
+		}
+	}
+
+	fn restore_pit(&mut self, pit: Self::PointInTime) {
+		self.inner.restore_pit(pit.inner);
+		self.state = pit.state;
+	}
+
+	fn advance(&mut self) -> Option<()> {
+
+		if self.state.byte_reached {
+			return None
+		}
+
+		let start = self.inner.pit().pos();
+		self.inner.advance()?;
+
+		self.state.pos = self.inner.pit().pos();
+		self.state.record_pos = None;
+
+		let b = self.byte().unwrap();
+		if b == self.quote {
+			self.state.in_quotes = !self.state.in_quotes;
+		} else if b == self.byte && !self.state.in_quotes {
+			self.state.byte_reached = true;
+			self.state.record_pos = Some(start);
+			return None
+		}
+
+		Some(())
+	}
+
+	fn recorder(&self) -> Option<&Recorder> {
+		self.inner.recorder()
+	}
+
+	fn recorder_depth(&self) -> usize {
+		self.inner.recorder_depth()
+	}
+
+	/// Does not allow to rewind before the start of the segment.
+	fn rewind(&mut self, len: usize) -> Result<&mut Self, usize> {
+		// the split byte is not part of the segment
+		let end = self.state.record_pos() + 1;
+		let start = self.state.start + 1;
+		let max = end - start;
+		if len > max {
+			return Err(max)
+		} else if len == 0 {
+			return Ok(self)
+		}
+
+		// the split byte needs to be rewound as well if it was reached
+		let reached_len = (self.state.pos + 1) - end;
+		self.inner.rewind(len + reached_len)?;
+
+		self.state.pos = self.inner.pit().pos();
+		self.state.byte_reached = false;
+		self.state.record_pos = None;
+
+		// recount the quotes before the new position
+		let quotes = self.slice()[start..self.state.pos + 1].iter()
+			.filter(|&&b| b == self.quote)
+			.count();
+		self.state.in_quotes = quotes % 2 == 1;
+
+		Ok(self)
+	}
+
+	#[inline]
+	unsafe fn is_valid_utf8() -> bool {
+		T::is_valid_utf8()
+	}
+
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+	use crate::*;
+
+	#[test]
+	fn csv() {
+
+		let mut parser = StrParser::new("a,\"b,c\",\"d\"\"e,\"\"\",,\"f\"");
+		let fields: Vec<_> = parser
+			.split_on_byte_respecting(b',', b'"')
+			.map_and_collect(|field| field.record().consume_to_str());
+
+		assert_eq!(fields, ["a", "\"b,c\"", "\"d\"\"e,\"\"\"", "", "\"f\""]);
+
+	}
+
+	#[test]
+	fn unterminated_quote() {
+
+		let mut parser = StrParser::new("a,\"b,c");
+		let fields: Vec<_> = parser
+			.split_on_byte_respecting(b',', b'"')
+			.map_and_collect(|field| field.record().consume_to_str());
+
+		assert_eq!(fields, ["a", "\"b,c"]);
+
+	}
+
+	#[test]
+	fn rewind_and_restore() {
+
+		let mut parser = StrParser::new("\"a,b\",c");
+		let mut split = parser.split_on_byte_respecting(b',', b'"');
+		let field = split.next().unwrap();
+
+		let pit = field.pit();
+		field.consume_len(2).unwrap();
+		assert!(field.in_quotes());
+		field.restore_pit(pit);
+		assert!(!field.in_quotes());
+
+		field.consume();
+		assert!(!field.in_quotes());
+		field.rewind(2).unwrap();
+		assert!(field.in_quotes());
+		assert_eq!(field.record().consume_to_str(), "b\"");
+
+		assert_eq!(split.next().unwrap().record().consume_to_str(), "c");
+		assert!(split.next().is_none());
+
+	}
+
+}
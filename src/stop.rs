@@ -40,6 +40,15 @@ where T: ParseIterator<'s> {
 		self.inner.recorder()
 	}
 
+	fn recorder_depth(&self) -> usize {
+		self.inner.recorder_depth()
+	}
+
+	fn rewind(&mut self, len: usize) -> Result<&mut Self, usize> {
+		self.inner.rewind(len)?;
+		Ok(self)
+	}
+
 	#[inline]
 	unsafe fn is_valid_utf8() -> bool {
 		T::is_valid_utf8()
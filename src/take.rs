@@ -0,0 +1,128 @@
+use crate::{
+	ParseIterator,
+	recorder::Recorder
+};
+
+#[derive(Debug)]
+pub struct Take<'a, T> {
+	inner: &'a mut T,
+	// offset after which nothing can be returned
+	end: usize
+}
+
+impl<'s, 'a, T> Take<'a, T>
+where T: ParseIterator<'s> {
+	pub(super) fn new(inner: &'a mut T, n: usize) -> Self {
+		let end = inner.offset().saturating_add(n);
+		Self {inner, end}
+	}
+}
+
+impl<'s, 'a, T> ParseIterator<'s> for Take<'a, T>
+where T: ParseIterator<'s> {
+
+	type PointInTime = T::PointInTime;
+
+	fn slice(&self) -> &'s [u8] {
+		self.inner.slice()
+	}
+
+	fn pit(&self) -> Self::PointInTime {
+		self.inner.pit()
+	}
+
+	fn restore_pit(&mut self, pit: Self::PointInTime) {
+		self.inner.restore_pit(pit)
+	}
+
+	fn advance(&mut self) -> Option<()> {
+		if self.inner.offset() >= self.end {
+			return None
+		}
+
+		let pit = self.inner.pit();
+		self.inner.advance()?;
+
+		// the inner iterator could have skipped bytes (see ignore_byte)
+		if self.inner.offset() > self.end {
+			self.inner.restore_pit(pit);
+			return None
+		}
+
+		Some(())
+	}
+
+	fn recorder(&self) -> Option<&Recorder> {
+		self.inner.recorder()
+	}
+
+	fn recorder_depth(&self) -> usize {
+		self.inner.recorder_depth()
+	}
+
+	fn rewind(&mut self, len: usize) -> Result<&mut Self, usize> {
+		self.inner.rewind(len)?;
+		Ok(self)
+	}
+
+	#[inline]
+	unsafe fn is_valid_utf8() -> bool {
+		T::is_valid_utf8()
+	}
+
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+	use crate::*;
+
+	#[test]
+	fn take() {
+
+		let mut parser = StrParser::new("\x05hello world");
+		let len = parser.next().unwrap() as usize;
+		let s = parser.take(len).record().consume_to_str();
+		assert_eq!(s, "hello");
+		// the parent moved exactly by the consumed amount
+		assert_eq!(parser.offset(), 6);
+		assert_eq!(parser.next(), Some(b' '));
+
+		// partial consumption
+		let mut parser = StrParser::new("abcdef");
+		let mut take = parser.take(4);
+		assert_eq!(take.next(), Some(b'a'));
+		assert_eq!(take.peek(), Some(b'b'));
+		assert_eq!(parser.offset(), 1);
+
+		// longer than the input
+		let mut parser = StrParser::new("ab");
+		assert_eq!(parser.take(5).consume_and_count(), 2);
+
+		let mut parser = StrParser::new("ab");
+		assert!(parser.take(0).next().is_none());
+
+	}
+
+	#[test]
+	fn take_with_combinators() {
+
+		let mut parser = StrParser::new("a,b,c,d");
+		let parts: Vec<_> = parser.take(3)
+			.split_on_byte(b',')
+			.map_and_collect(|p| p.record().consume_to_str());
+		assert_eq!(parts, ["a", "b"]);
+		assert_eq!(parser.remaining_str(), ",c,d");
+
+		// ignored bytes don't go past the end
+		let mut parser = StrParser::new("ab  c");
+		let mut ignore = parser.ignore_byte(b' ');
+		let count = ignore.take(3).consume_and_count();
+		assert_eq!(count, 2);
+		assert_eq!(parser.remaining_str(), "  c");
+
+	}
+
+}
@@ -0,0 +1,279 @@
+//!
+//! A small lexer driver built from a list of rules.
+//!
+//! ## Example
+//! ```
+//! # use byte_parser::{StrParser, tokenizer::Tokenizer, position::Span};
+//! #[derive(Debug, Clone, Copy, PartialEq)]
+//! enum Kind { Num, Plus }
+//!
+//! let tokenizer = Tokenizer::builder()
+//! 	.skip_while_byte_fn(u8::is_ascii_whitespace)
+//! 	.while_byte_fn(Kind::Num, u8::is_ascii_digit)
+//! 	.slice(Kind::Plus, b"+")
+//! 	.build();
+//!
+//! let mut parser = StrParser::new("1 + 23");
+//! let tokens: Vec<_> = tokenizer.tokens(&mut parser)
+//! 	.collect::<Result<_, _>>().unwrap();
+//! assert_eq!(tokens, [
+//! 	(Span::new(0, 1), Kind::Num, "1"),
+//! 	(Span::new(2, 3), Kind::Plus, "+"),
+//! 	(Span::new(4, 6), Kind::Num, "23")
+//! ]);
+//! ```
+
+use crate::{
+	ParseIterator,
+	position::Span,
+	pit::PointInTime,
+	error::ParseError
+};
+
+use core::fmt;
+use core::marker::PhantomData;
+
+use alloc::{vec, vec::Vec, boxed::Box};
+
+
+enum Matcher {
+	Slice(Vec<u8>),
+	WhileByteFn(Box<dyn Fn(&u8) -> bool>)
+}
+
+impl fmt::Debug for Matcher {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Slice(s) => f.debug_tuple("Slice").field(s).finish(),
+			Self::WhileByteFn(_) => f.write_str("WhileByteFn(..)")
+		}
+	}
+}
+
+impl Matcher {
+	/// Returns `true` if something was consumed.
+	fn consume<'s, I>(&self, iter: &mut I) -> bool
+	where I: ParseIterator<'s> {
+		match self {
+			Self::Slice(s) => iter.expect_bytes(s).is_ok(),
+			Self::WhileByteFn(f) => iter.while_byte_fn(f).consume_and_count() > 0
+		}
+	}
+}
+
+#[derive(Debug)]
+struct Rule<K> {
+	matcher: Matcher,
+	// None means the match get's skipped
+	kind: Option<K>
+}
+
+/// Builder to create a `Tokenizer`.
+///
+/// Rules are tried in the order they were added,
+/// the first rule that matches wins.
+#[derive(Debug)]
+pub struct TokenizerBuilder<K> {
+	rules: Vec<Rule<K>>
+}
+
+impl<K> TokenizerBuilder<K> {
+
+	fn rule(mut self, matcher: Matcher, kind: Option<K>) -> Self {
+		self.rules.push(Rule {matcher, kind});
+		self
+	}
+
+	/// Adds a rule which matches the given slice.
+	pub fn slice(self, kind: K, slice: &[u8]) -> Self {
+		self.rule(Matcher::Slice(slice.to_vec()), Some(kind))
+	}
+
+	/// Adds a rule which matches at least one byte for which `f` returns
+	/// `true`.
+	pub fn while_byte_fn<F>(self, kind: K, f: F) -> Self
+	where F: Fn(&u8) -> bool + 'static {
+		self.rule(Matcher::WhileByteFn(Box::new(f)), Some(kind))
+	}
+
+	/// Adds a rule which skips the given slice.
+	pub fn skip_slice(self, slice: &[u8]) -> Self {
+		self.rule(Matcher::Slice(slice.to_vec()), None)
+	}
+
+	/// Adds a rule which skips bytes for which `f` returns `true`.
+	pub fn skip_while_byte_fn<F>(self, f: F) -> Self
+	where F: Fn(&u8) -> bool + 'static {
+		self.rule(Matcher::WhileByteFn(Box::new(f)), None)
+	}
+
+	/// Creates the `Tokenizer`.
+	pub fn build(self) -> Tokenizer<K> {
+		Tokenizer { rules: self.rules }
+	}
+
+}
+
+/// Splits a `ParseIterator` into tokens.
+#[derive(Debug)]
+pub struct Tokenizer<K> {
+	rules: Vec<Rule<K>>
+}
+
+impl<K> Tokenizer<K> {
+
+	/// Creates a new `TokenizerBuilder`.
+	pub fn builder() -> TokenizerBuilder<K> {
+		TokenizerBuilder { rules: vec![] }
+	}
+
+	/// Returns an iterator over all tokens in `iter`.
+	///
+	/// If no rule matches an error is returned and the iteration stops.
+	pub fn tokens<'t, 'a, 's, I>(&'t self, iter: &'a mut I) -> Tokens<'t, 'a, 's, I, K>
+	where I: ParseIterator<'s> {
+		Tokens {
+			tokenizer: self,
+			iter,
+			failed: false,
+			marker: PhantomData
+		}
+	}
+
+}
+
+/// Iterator returned by `Tokenizer::tokens`.
+///
+/// ## Panics
+/// Panics if a token contains invalid utf8.
+#[derive(Debug)]
+pub struct Tokens<'t, 'a, 's, I, K> {
+	tokenizer: &'t Tokenizer<K>,
+	iter: &'a mut I,
+	failed: bool,
+	marker: PhantomData<&'s str>
+}
+
+impl<'t, 'a, 's, I, K> Iterator for Tokens<'t, 'a, 's, I, K>
+where
+	I: ParseIterator<'s>,
+	K: Clone {
+
+	type Item = Result<(Span, K, &'s str), ParseError>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.failed {
+			return None
+		}
+
+		'tokens: loop {
+			self.iter.peek()?;
+
+			let start = self.iter.pit().record_pos() + 1;
+
+			for rule in &self.tokenizer.rules {
+				let mut rec = self.iter.record();
+				if !rule.matcher.consume(&mut rec) {
+					continue
+				}
+
+				let kind = match &rule.kind {
+					Some(k) => k.clone(),
+					None => continue 'tokens
+				};
+				let s = rec.to_str();
+
+				let span = Span::new(start, start + s.len());
+				return Some(Ok((span, kind, s)))
+			}
+
+			self.failed = true;
+			let err = ParseError::new(start, self.iter.peek())
+				.with_expected("token");
+			return Some(Err(err))
+		}
+	}
+
+}
+
+
+#[cfg(test)]
+mod tests {
+
+	use super::*;
+	use crate::*;
+
+	#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+	enum Kind {
+		Num,
+		Ident,
+		Op,
+		Pow,
+		Open,
+		Close
+	}
+
+	fn tokenizer() -> Tokenizer<Kind> {
+		Tokenizer::builder()
+			.skip_while_byte_fn(u8::is_ascii_whitespace)
+			.while_byte_fn(Kind::Num, u8::is_ascii_digit)
+			.while_byte_fn(Kind::Ident, u8::is_ascii_alphabetic)
+			// ** needs to be before *
+			.slice(Kind::Pow, b"**")
+			.while_byte_fn(Kind::Op, |b| b"+-*/".contains(b))
+			.slice(Kind::Open, b"(")
+			.slice(Kind::Close, b")")
+			.build()
+	}
+
+	#[test]
+	fn arithmetic() {
+
+		let tokenizer = tokenizer();
+		let mut parser = StrParser::new("12 * (x + 3)**2");
+		let tokens: Vec<_> = tokenizer.tokens(&mut parser)
+			.map(|r| r.map(|(span, kind, s)| (span.start, kind, s)))
+			.collect::<Result<_, _>>()
+			.unwrap();
+
+		assert_eq!(tokens, [
+			(0, Kind::Num, "12"),
+			(3, Kind::Op, "*"),
+			(5, Kind::Open, "("),
+			(6, Kind::Ident, "x"),
+			(8, Kind::Op, "+"),
+			(10, Kind::Num, "3"),
+			(11, Kind::Close, ")"),
+			(12, Kind::Pow, "**"),
+			(14, Kind::Num, "2")
+		]);
+		assert!(parser.next().is_none());
+
+	}
+
+	#[test]
+	fn unknown_token() {
+
+		let tokenizer = tokenizer();
+		let mut parser = StrParser::new("1 + $");
+		let mut tokens = tokenizer.tokens(&mut parser);
+
+		assert!(tokens.next().unwrap().is_ok());
+		assert!(tokens.next().unwrap().is_ok());
+		let err = tokens.next().unwrap().unwrap_err();
+		assert_eq!(err.position(), 4);
+		assert_eq!(err.found(), Some(b'$'));
+		assert!(tokens.next().is_none());
+
+	}
+
+	#[test]
+	fn only_skipped() {
+
+		let tokenizer = tokenizer();
+		let mut parser = StrParser::new("  \n ");
+		assert!(tokenizer.tokens(&mut parser).next().is_none());
+
+	}
+
+}
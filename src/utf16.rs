@@ -0,0 +1,182 @@
+//!
+//! Decode UTF-16 encoded input to parse it as UTF-8.
+//!
+//! `Utf16Decoded` is not a parser, it decodes the whole input eagerly into
+//! an owned `String` when it is created. `parser` then returns a
+//! `StrParser` over that buffer. Decoding lazily while advancing is not
+//! possible since a `ParseIterator` returns slices which live as long as
+//! the full input, so the buffer can't grow while it is borrowed.
+//!
+//! ## Example
+//! ```
+//! # use byte_parser::{ParseIterator, utf16::Utf16Decoded};
+//! // "hi 😀" in UTF-16LE with a BOM
+//! let bytes = [0xFF, 0xFE, b'h', 0, b'i', 0, b' ', 0, 0x3D, 0xD8, 0x00, 0xDE];
+//! let decoded = Utf16Decoded::from_bytes(&bytes).unwrap();
+//!
+//! let mut parser = decoded.parser();
+//! let mut split = parser.split_on_byte(b' ');
+//! let hi = split.next().unwrap().record().consume_to_str();
+//! let smiley = split.next().unwrap().record().consume_to_str();
+//! assert_eq!(hi, "hi");
+//! assert_eq!(smiley, "😀");
+//! ```
+
+use crate::StrParser;
+
+use core::char;
+
+use alloc::{string::String, vec::Vec};
+
+
+/// Error returned if the input is not valid UTF-16.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Utf16Error {
+	/// The input has an odd number of bytes.
+	OddLength,
+	/// A surrogate was found which is not part of a pair.
+	UnpairedSurrogate(u16)
+}
+
+/// Owns UTF-16 input decoded as UTF-8.
+///
+/// The input is decoded completely when calling one of the constructors.
+/// Every `StrParser` returned by `parser` borrows from this buffer, so
+/// recorded slices and strs can't outlive it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Utf16Decoded {
+	decoded: String
+}
+
+impl Utf16Decoded {
+
+	/// Decodes UTF-16 code units. A leading BOM is removed.
+	pub fn from_units(units: &[u16]) -> Result<Self, Utf16Error> {
+		let units = match units.first() {
+			Some(0xFEFF) => &units[1..],
+			_ => units
+		};
+		Self::decode(units.iter().copied())
+	}
+
+	/// Decodes UTF-16LE encoded bytes.
+	pub fn from_le_bytes(bytes: &[u8]) -> Result<Self, Utf16Error> {
+		Self::from_bytes_with(bytes, u16::from_le_bytes)
+	}
+
+	/// Decodes UTF-16BE encoded bytes.
+	pub fn from_be_bytes(bytes: &[u8]) -> Result<Self, Utf16Error> {
+		Self::from_bytes_with(bytes, u16::from_be_bytes)
+	}
+
+	/// Decodes UTF-16 encoded bytes, the endianness is detected with the BOM.
+	/// If there is no BOM little endian is assumed.
+	pub fn from_bytes(bytes: &[u8]) -> Result<Self, Utf16Error> {
+		match bytes {
+			[0xFE, 0xFF, rest @ ..] => Self::from_be_bytes(rest),
+			[0xFF, 0xFE, rest @ ..] => Self::from_le_bytes(rest),
+			_ => Self::from_le_bytes(bytes)
+		}
+	}
+
+	fn from_bytes_with(
+		bytes: &[u8],
+		f: fn([u8; 2]) -> u16
+	) -> Result<Self, Utf16Error> {
+		if bytes.len() % 2 != 0 {
+			return Err(Utf16Error::OddLength)
+		}
+
+		let units: Vec<u16> = bytes.chunks_exact(2)
+			.map(|c| f([c[0], c[1]]))
+			.collect();
+		Self::from_units(&units)
+	}
+
+	fn decode<I>(units: I) -> Result<Self, Utf16Error>
+	where I: Iterator<Item=u16> {
+		char::decode_utf16(units)
+			.map(|r| {
+				r.map_err(|e| Utf16Error::UnpairedSurrogate(e.unpaired_surrogate()))
+			})
+			.collect::<Result<String, _>>()
+			.map(|decoded| Self {decoded})
+	}
+
+	/// Returns the decoded text.
+	pub fn as_str(&self) -> &str {
+		&self.decoded
+	}
+
+	/// Returns a new `StrParser` over the decoded text.
+	pub fn parser(&self) -> StrParser<'_> {
+		StrParser::new(&self.decoded)
+	}
+
+}
+
+
+#[cfg(test)]
+mod tests {
+
+	use super::*;
+	use crate::ParseIterator;
+
+	fn le(s: &str) -> Vec<u8> {
+		s.encode_utf16().flat_map(u16::to_le_bytes).collect()
+	}
+
+	fn be(s: &str) -> Vec<u8> {
+		s.encode_utf16().flat_map(u16::to_be_bytes).collect()
+	}
+
+	#[test]
+	fn decode_bom() {
+
+		let s = "key: välue 😀";
+
+		let mut bytes = vec![0xFF, 0xFE];
+		bytes.extend(le(s));
+		assert_eq!(Utf16Decoded::from_bytes(&bytes).unwrap().as_str(), s);
+
+		let mut bytes = vec![0xFE, 0xFF];
+		bytes.extend(be(s));
+		assert_eq!(Utf16Decoded::from_bytes(&bytes).unwrap().as_str(), s);
+
+		// no bom
+		assert_eq!(Utf16Decoded::from_bytes(&le(s)).unwrap().as_str(), s);
+		assert_eq!(Utf16Decoded::from_be_bytes(&be(s)).unwrap().as_str(), s);
+
+	}
+
+	#[test]
+	fn decode_errors() {
+
+		assert_eq!(
+			Utf16Decoded::from_le_bytes(&[b'a', 0, b'b']).unwrap_err(),
+			Utf16Error::OddLength
+		);
+
+		// lonely high surrogate
+		assert_eq!(
+			Utf16Decoded::from_units(&[0xD83D, b'a' as u16]).unwrap_err(),
+			Utf16Error::UnpairedSurrogate(0xD83D)
+		);
+
+	}
+
+	#[test]
+	fn parse_decoded() {
+
+		let decoded = Utf16Decoded::from_le_bytes(&le("a😀b")).unwrap();
+		let mut parser = decoded.parser();
+		assert_eq!(parser.next(), Some(b'a'));
+		let s = parser.record()
+			.consume_len(4).unwrap()
+			.to_str();
+		assert_eq!(s, "😀");
+		assert_eq!(parser.next(), Some(b'b'));
+
+	}
+
+}
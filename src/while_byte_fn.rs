@@ -1,7 +1,8 @@
 
 use crate::{
 	ParseIterator,
-	recorder::Recorder
+	recorder::Recorder,
+	parse_iterator::skip_unchecked
 };
 
 #[derive(Debug)]
@@ -48,10 +49,35 @@ where
 		}
 	}
 
+	fn consume(&mut self) -> &mut Self {
+		if unsafe { T::advances_by_one() } {
+			let f = &self.f;
+			let rest = self.inner.remaining();
+			let len = rest.iter()
+				.position(|b| !f(b))
+				.unwrap_or(rest.len());
+			// safe because advances_by_one returned true
+			unsafe { skip_unchecked(self.inner, len) };
+			return self
+		}
+
+		while self.advance().is_some() {}
+		self
+	}
+
 	fn recorder(&self) -> Option<&Recorder> {
 		self.inner.recorder()
 	}
 
+	fn recorder_depth(&self) -> usize {
+		self.inner.recorder_depth()
+	}
+
+	fn rewind(&mut self, len: usize) -> Result<&mut Self, usize> {
+		self.inner.rewind(len)?;
+		Ok(self)
+	}
+
 	#[inline]
 	unsafe fn is_valid_utf8() -> bool {
 		T::is_valid_utf8()
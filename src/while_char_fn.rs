@@ -0,0 +1,144 @@
+
+use crate::{
+	ParseIterator,
+	recorder::Recorder,
+	parse_iterator::utf8_char_width
+};
+
+#[derive(Debug)]
+pub struct WhileCharFn<'a, T, F> {
+	inner: &'a mut T,
+	f: F
+}
+
+impl<'a, T, F> WhileCharFn<'a, T, F> {
+	pub(super) fn new(inner: &'a mut T, f: F) -> Self {
+		Self {inner, f}
+	}
+}
+
+impl<'s, 'a, T, F> ParseIterator<'s> for WhileCharFn<'a, T, F>
+where
+	T: ParseIterator<'s>,
+	F: Fn(char) -> bool {
+
+	type PointInTime = T::PointInTime;
+
+	fn slice(&self) -> &'s [u8] {
+		self.inner.slice()
+	}
+
+	fn pit(&self) -> Self::PointInTime {
+		self.inner.pit()
+	}
+
+	fn restore_pit(&mut self, pit: Self::PointInTime) {
+		self.inner.restore_pit(pit)
+	}
+
+	// advances over a complete char at once, so a continuation byte
+	// is never accepted on its own
+	fn advance(&mut self) -> Option<()> {
+		let pit = self.inner.pit();
+		let b = self.inner.next()?;
+
+		let start = self.inner.offset() - 1;
+		let slice = self.inner.slice();
+		let end = (start + utf8_char_width(b)).min(slice.len());
+
+		// a stray continuation byte is invalid utf8
+		let c = core::str::from_utf8(&slice[start..end]).ok()
+			.and_then(|s| s.chars().next());
+
+		match c {
+			Some(c) if (self.f)(c) => {
+				// the rest of the char was already validated
+				self.inner.consume_len(c.len_utf8() - 1).ok()?;
+				Some(())
+			},
+			_ => {
+				self.inner.restore_pit(pit);
+				None
+			}
+		}
+	}
+
+	fn recorder(&self) -> Option<&Recorder> {
+		self.inner.recorder()
+	}
+
+	fn recorder_depth(&self) -> usize {
+		self.inner.recorder_depth()
+	}
+
+	fn rewind(&mut self, len: usize) -> Result<&mut Self, usize> {
+		self.inner.rewind(len)?;
+		Ok(self)
+	}
+
+	#[inline]
+	unsafe fn is_valid_utf8() -> bool {
+		T::is_valid_utf8()
+	}
+
+}
+
+
+
+
+#[cfg(test)]
+mod tests {
+
+	use crate::*;
+
+	#[test]
+	fn while_char_fn() {
+
+		let mut parser = StrParser::new("grüße welt");
+		let word = parser
+			.record()
+			.while_char_fn(char::is_alphabetic)
+			.consume_to_str();
+		assert_eq!(word, "grüße");
+		assert_eq!(parser.next(), Some(b' '));
+
+		// ü is advanced over as one unit
+		let mut parser = StrParser::new("üa");
+		let n = parser.while_char_fn(|c| c == 'ü').consume_and_count();
+		assert_eq!(n, 1);
+		assert_eq!(parser.next(), Some(b'a'));
+
+		let mut parser = StrParser::new("aü");
+		let n = parser.while_char_fn(|c| c != 'ü').consume_and_count();
+		assert_eq!(n, 1);
+		assert_eq!(parser.next_char(), Some('ü'));
+
+	}
+
+	#[test]
+	fn invalid_utf8_stops() {
+
+		let mut parser = Parser::new(b"ab\xffc");
+		parser.consume_while_char_fn(|_| true);
+		assert_eq!(parser.next(), Some(0xff));
+
+		// cut off char
+		let mut parser = Parser::new(b"a\xc3");
+		parser.consume_while_char_fn(|_| true);
+		assert_eq!(parser.next(), Some(0xc3));
+
+		// stray continuation byte
+		let mut parser = Parser::new(b"\x80abc");
+		assert_eq!(parser.while_char_fn(|_| false).consume_and_count(), 0);
+		assert_eq!(parser.while_char_fn(|_| true).consume_and_count(), 0);
+		assert_eq!(parser.next(), Some(0x80));
+
+		// starting in the middle of a char
+		let mut parser = StrParser::new("üa");
+		parser.advance();
+		assert_eq!(parser.while_char_fn(|_| true).consume_and_count(), 0);
+		assert_eq!(parser.offset(), 1);
+
+	}
+
+}